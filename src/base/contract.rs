@@ -1,4 +1,4 @@
-use soroban_sdk::{contract, contractimpl, Env};
+use soroban_sdk::{contract, contractimpl, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
 
 use self::{storage::get_lockup_by_id, utils::calculate_additional_time};
 
@@ -6,330 +6,3031 @@ use super::*;
 
 use interface::IFluxity;
 
+const MAX_THRESHOLD_SCAN: u32 = 100;
+const MAX_BATCH_SIZE: u32 = 20;
+const MAX_RANGE_LIMIT: u32 = 100;
+const MAX_NONCES_PER_LOCKUP: u32 = 10;
+const MAX_DETAIL_PAGE_SIZE: u32 = 10;
+const MAX_SCHEDULE_STEPS: u32 = 100;
+const DEFAULT_DORMANCY_PERIOD: u64 = 2 * 365 * 24 * 60 * 60;
+const LEDGER_CLOSE_SECONDS: u64 = 5;
+
 #[contract]
 pub struct Fluxity;
 
 #[contractimpl]
 impl IFluxity for Fluxity {
-    /// Returns the latest stream id
+    /// Sets the admin of the contract along with the creation fee and the minimum
+    /// lockup duration, can only be called once and has to be authorized by the admin
     ///
     /// # Examples
     ///
     /// ```
-    /// let id = fluxity_client::get_latest_stream_id();
+    /// let admin = Address::random(&env);
+    /// let config = ContractConfig {
+    ///     fee_bps: 25,
+    ///     fee_recipient: Address::random(&env),
+    ///     min_duration: 86400,
+    /// };
+    ///
+    /// fluxity_client::initialize(&admin, &config);
     /// ```
-    fn get_latest_lockup_id(e: Env) -> u64 {
-        storage::get_latest_lockup_id(&e)
+    fn initialize(
+        e: Env,
+        admin: Address,
+        config: types::ContractConfig,
+    ) -> Result<(), errors::CustomErrors> {
+        if storage::has_admin(&e) {
+            return Err(errors::CustomErrors::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+
+        if config.fee_bps > 10000 {
+            return Err(errors::CustomErrors::InvalidFeeConfig);
+        }
+
+        storage::set_admin(&e, &admin);
+        storage::set_min_duration(&e, &config.min_duration);
+
+        events::publish_admin_changed_event(&e, &admin);
+
+        if config.fee_bps > 0 {
+            let fee_config = types::FeeConfig {
+                fee_bps: config.fee_bps,
+                fee_recipient: config.fee_recipient,
+                fee_token: types::FeeToken::None,
+                cancel_fee_bps: 0,
+                fee_strategy: types::FeeStrategy::None,
+            };
+
+            storage::set_fee_config(&e, &fee_config);
+
+            events::publish_fee_config_changed_event(&e, &fee_config);
+        }
+
+        Ok(())
     }
 
-    /// Returns a lockup by id
+    /// Returns the minimum duration of new lockups in seconds, 0 if there's none
     ///
     /// # Examples
     ///
     /// ```
-    /// let lockup_id = 20;
-    ///
-    /// fluxity_client::get_lockup(&stream_id);
+    /// let min_duration = fluxity_client::get_min_duration();
     /// ```
-    fn get_lockup(e: Env, id: u64) -> Result<types::Lockup, errors::CustomErrors> {
-        match e.storage().persistent().get(&data_key::DataKey::Lockup(id)) {
-            None => Err(errors::CustomErrors::LockupNotFound),
-            Some(lockup) => Ok(lockup),
-        }
+    fn get_min_duration(e: Env) -> u64 {
+        storage::get_min_duration(&e)
     }
 
-    /// Creates an stream
+    /// Sets the maximum duration of new lockups in seconds, requires the admin's auth.
+    /// It can't be below the minimum duration
     ///
     /// # Examples
     ///
     /// ```
-    /// let params = StreamInput {
-    ///     sender: Address::random(&env),
-    ///     receiver: Address::random(&env),
-    ///     token: Address::random(&env),
-    ///     amount: 20000000,
-    ///     start_date: now,
-    ///     cancellable_date: now,
-    ///     cliff_date: now + 100,
-    ///     end_date: now + 1000,
-    ///     rate: Rate::Daily
-    /// };
+    /// let ten_years = 10 * 365 * 24 * 60 * 60;
     ///
-    /// fluxity_client::create_stream(&params);
+    /// fluxity_client::set_max_duration(&ten_years);
     /// ```
-    fn create_stream(e: Env, params: types::LockupInput) -> Result<u64, errors::CustomErrors> {
-        if params.amount <= 0 {
-            return Err(errors::CustomErrors::InvalidAmount);
-        }
-
-        if &params.sender == &params.receiver {
-            return Err(errors::CustomErrors::InvalidReceiver);
-        }
-
-        if &params.start_date >= &params.end_date {
-            return Err(errors::CustomErrors::InvalidStartDate);
-        }
+    fn set_max_duration(e: Env, max_duration: u64) -> Result<(), errors::CustomErrors> {
+        let admin = storage::get_admin(&e)?;
 
-        if &params.cancellable_date > &params.end_date {
-            return Err(errors::CustomErrors::InvalidCancellableDate);
-        }
+        admin.require_auth();
 
-        if &params.cliff_date < &params.start_date || &params.cliff_date > &params.end_date {
-            return Err(errors::CustomErrors::InvalidCliffDate);
+        if max_duration == 0 || max_duration < storage::get_min_duration(&e) {
+            return Err(errors::CustomErrors::InvalidDuration);
         }
 
-        token::transfer_from(&e, &params.token, &params.sender, &params.amount);
-
-        let id = storage::get_latest_lockup_id(&e);
-        let mut lockup: types::Lockup = params.into();
+        storage::set_max_duration(&e, &max_duration);
 
-        lockup.is_vesting = false;
+        events::publish_max_duration_changed_event(&e, max_duration);
 
-        storage::set_lockup(&e, id, &lockup);
-        storage::increment_latest_lockup_id(&e, &id);
-        events::publish_lockup_created_event(&e, id);
+        Ok(())
+    }
 
-        Ok(id)
+    /// Returns the maximum duration of new lockups in seconds, none if there's no limit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let max_duration = fluxity_client::get_max_duration();
+    /// ```
+    fn get_max_duration(e: Env) -> Option<u64> {
+        storage::get_max_duration(&e)
     }
 
-    /// Cancels a lockup
+    /// Sets the fees charged on lockup creation and cancellation, only callable by the
+    /// admin. Without a fee token the creation fee is skimmed from the lockup amount,
+    /// otherwise it's pulled separately in the fee token. With a fee strategy, the
+    /// creation fee is computed by that contract instead of `fee_bps`. The
+    /// cancellation fee is taken from the sender's refund
     ///
     /// # Examples
     ///
     /// ```
-    /// let lockup_id = 20;
+    /// let config = FeeConfig {
+    ///     fee_bps: 25,
+    ///     fee_recipient: Address::random(&env),
+    ///     fee_token: FeeToken::None,
+    ///     cancel_fee_bps: 50,
+    ///     fee_strategy: FeeStrategy::None,
+    /// };
     ///
-    /// fluxity_client::cancel_lockup(&lockup_id);
+    /// fluxity_client::set_fee_config(&config);
     /// ```
-    fn cancel_lockup(e: Env, id: u64) -> Result<(i128, i128), errors::CustomErrors> {
-        let mut lockup = storage::get_lockup_by_id(&e, &id).unwrap();
-
-        lockup.sender.require_auth();
-
-        if lockup.is_cancelled {
-            return Err(errors::CustomErrors::LockupAlreadyCanceled);
-        }
+    fn set_fee_config(e: Env, config: types::FeeConfig) -> Result<(), errors::CustomErrors> {
+        let admin = storage::get_admin(&e)?;
 
-        let current_date = e.ledger().timestamp();
+        admin.require_auth();
 
-        if lockup.end_date <= current_date {
-            return Err(errors::CustomErrors::LockupAlreadySettled);
+        if config.fee_bps > 10000 || config.cancel_fee_bps > 10000 {
+            return Err(errors::CustomErrors::InvalidFeeConfig);
         }
 
-        if lockup.cancellable_date > current_date {
-            return Err(errors::CustomErrors::LockupNotCancellableYet);
-        }
+        storage::set_fee_config(&e, &config);
 
-        let mut amounts = utils::calculate_stream_amounts(
-            lockup.start_date,
-            lockup.end_date,
-            lockup.cliff_date,
-            current_date,
-            lockup.amount,
-        );
+        events::publish_fee_config_changed_event(&e, &config);
 
-        if lockup.is_vesting {
-            amounts = utils::calculate_vesting_amounts(
-                lockup.start_date,
-                lockup.end_date,
-                lockup.cliff_date,
-                current_date,
-                lockup.rate,
-                lockup.amount,
-            );
-        }
+        Ok(())
+    }
 
-        let sender_amount = amounts.sender_amount;
-        let receiver_amount = amounts.receiver_amount - lockup.withdrawn;
+    /// Returns the fee config, if fees are enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fluxity_client::get_fee_config();
+    /// ```
+    fn get_fee_config(e: Env) -> Option<types::FeeConfig> {
+        storage::get_fee_config(&e)
+    }
 
-        lockup.is_cancelled = true;
-        lockup.cancelled_date = current_date;
-        lockup.withdrawn = amounts.receiver_amount;
+    /// Returns the total creation and cancellation fees collected in a token
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let token = Address::random(&env);
+    ///
+    /// fluxity_client::get_fees_collected(&token);
+    /// ```
+    fn get_fees_collected(e: Env, token: Address) -> i128 {
+        storage::get_fees_collected(&e, &token)
+    }
 
-        storage::set_lockup(&e, id, &lockup);
+    /// Returns a bitmask of the optional features enabled on this deployment, see
+    /// the `FEATURE_*` constants of the types
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let features = fluxity_client::get_features();
+    ///
+    /// let has_fees = features & FEATURE_FEES != 0;
+    /// ```
+    fn get_features(e: Env) -> u32 {
+        // Anyone can register a template, so they're always available
+        let mut features = types::FEATURE_TEMPLATES;
 
-        if receiver_amount > 0 {
-            token::transfer(&e, &lockup.token, &lockup.receiver, &receiver_amount);
+        if storage::get_fee_config(&e).is_some() {
+            features |= types::FEATURE_FEES;
         }
 
-        if sender_amount > 0 {
-            token::transfer(&e, &lockup.token, &lockup.sender, &sender_amount);
+        if storage::get_position_nft(&e).is_some() {
+            features |= types::FEATURE_TOKENIZED_POSITIONS;
         }
 
-        events::publish_lockup_cancelled_event(&e, id);
-
-        Ok((sender_amount, receiver_amount))
+        features
     }
 
-    /// Withdraws from a lockup, anyone call call this function even for others
+    /// Sets the NFT contract minting the positions of tokenized lockups, only
+    /// callable by the admin
     ///
     /// # Examples
     ///
     /// ```
-    /// let lockup_id = 20;
-    /// let amount_to_withdraw = 30000000 // Represents 3 in a 7-decimal token
+    /// let nft = Address::random(&env);
     ///
-    /// fluxity_client::withdraw_lockup(&stream_id, &amount_to_withdraw);
+    /// fluxity_client::set_position_nft(&nft);
     /// ```
-    fn withdraw_lockup(e: Env, id: u64, amount: i128) -> Result<i128, errors::CustomErrors> {
-        let mut lockup = storage::get_lockup_by_id(&e, &id).unwrap();
+    fn set_position_nft(e: Env, nft: Address) -> Result<(), errors::CustomErrors> {
+        let admin = storage::get_admin(&e)?;
 
-        if amount < 0 {
-            return Err(errors::CustomErrors::AmountUnderflows);
-        }
+        admin.require_auth();
 
-        if lockup.is_cancelled {
-            return Err(errors::CustomErrors::LockupIsCanceled);
-        }
+        storage::set_position_nft(&e, &nft);
 
-        let current_date = e.ledger().timestamp();
+        events::publish_position_nft_changed_event(&e, &nft);
 
-        if current_date <= lockup.start_date {
-            return Err(errors::CustomErrors::LockupNotStartedYet);
-        }
+        Ok(())
+    }
 
-        if current_date <= lockup.cliff_date {
-            return Ok(0);
-        }
+    /// Sets how long after the end date a lockup must stay untouched before the
+    /// sender can reclaim what the receiver left in it, requires the admin's auth
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let one_year = 365 * 24 * 60 * 60;
+    ///
+    /// fluxity_client::set_dormancy_period(&one_year);
+    /// ```
+    fn set_dormancy_period(e: Env, period: u64) -> Result<(), errors::CustomErrors> {
+        let admin = storage::get_admin(&e)?;
 
-        let mut amounts = utils::calculate_stream_amounts(
-            lockup.start_date,
-            lockup.end_date,
-            lockup.cliff_date,
-            current_date,
-            lockup.amount,
-        );
+        admin.require_auth();
 
-        if lockup.is_vesting {
-            amounts = utils::calculate_vesting_amounts(
-                lockup.start_date,
-                lockup.end_date,
-                lockup.cliff_date,
-                current_date,
-                lockup.rate,
-                lockup.amount,
-            );
-        }
+        storage::set_dormancy_period(&e, &period);
 
-        let withdrawable = amounts.receiver_amount - lockup.withdrawn;
+        events::publish_dormancy_period_changed_event(&e, period);
 
-        if withdrawable < amount {
-            return Err(errors::CustomErrors::SpecifiedAmountIsGreaterThanWithdrawable);
-        }
+        Ok(())
+    }
 
-        let mut amount_to_transfer = amount;
+    /// Returns the dormancy period, which defaults to two years until the admin sets it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let period = fluxity_client::get_dormancy_period();
+    /// ```
+    fn get_dormancy_period(e: Env) -> u64 {
+        storage::get_dormancy_period(&e).unwrap_or(DEFAULT_DORMANCY_PERIOD)
+    }
 
-        if amount == 0 {
-            amount_to_transfer = withdrawable;
-        }
+    /// Sets the rate stored on the streams created afterwards with `use_default_rate`
+    /// in place of the given one, requires the admin's auth. A stream's rate is only for display, it
+    /// accrues continuously whatever the rate, so only vestings step with theirs
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// fluxity_client::set_default_rate(&Rate::Monthly);
+    /// ```
+    fn set_default_rate(e: Env, rate: types::Rate) -> Result<(), errors::CustomErrors> {
+        let admin = storage::get_admin(&e)?;
 
-        lockup.withdrawn = lockup.withdrawn + amount_to_transfer;
+        admin.require_auth();
 
-        storage::set_lockup(&e, id, &lockup);
+        storage::set_default_rate(&e, &rate);
 
-        token::transfer(&e, &lockup.token, &lockup.receiver, &amount_to_transfer);
+        events::publish_default_rate_changed_event(&e, rate);
 
-        events::publish_lockup_withdrawn_event(&e, id);
+        Ok(())
+    }
 
-        Ok(amount_to_transfer)
+    /// Returns the rate stored on new streams with `use_default_rate`, none until the
+    /// admin sets it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let rate = fluxity_client::get_default_rate();
+    /// ```
+    fn get_default_rate(e: Env) -> Option<types::Rate> {
+        storage::get_default_rate(&e)
     }
 
-    /// Creates a vesting stream
+    /// Sets the thresholds of the small, medium and large buckets withdrawals are
+    /// classified in by the metrics events, requires the admin's auth. No metrics
+    /// events are emitted until they're set
     ///
     /// # Examples
     ///
     /// ```
-    /// let params = VestingInput {
-    ///     sender: Address::random(&env),
-    ///     receiver: Address::random(&env),
-    ///     token: Address::random(&env),
-    ///     amount: 20000000,
-    ///     start_date: now,
-    ///     cancellable_date: now,
-    ///     cliff_date: now + 100,
-    ///     end_date: now + 1000,
-    ///     rate: Rate::Daily
+    /// let buckets = WithdrawalBuckets {
+    ///     medium_from: 1000000,
+    ///     large_from: 100000000,
     /// };
     ///
-    /// fluxity_client::create_vesting(&params);
+    /// fluxity_client::set_withdrawal_buckets(&buckets);
     /// ```
-    fn create_vesting(e: Env, params: types::LockupInput) -> Result<u64, errors::CustomErrors> {
-        if params.amount <= 0 {
+    fn set_withdrawal_buckets(
+        e: Env,
+        buckets: types::WithdrawalBuckets,
+    ) -> Result<(), errors::CustomErrors> {
+        let admin = storage::get_admin(&e)?;
+
+        admin.require_auth();
+
+        if buckets.medium_from <= 0 || buckets.large_from < buckets.medium_from {
             return Err(errors::CustomErrors::InvalidAmount);
         }
 
-        if &params.sender == &params.receiver {
-            return Err(errors::CustomErrors::InvalidReceiver);
-        }
+        storage::set_withdrawal_buckets(&e, &buckets);
 
-        if &params.start_date >= &params.end_date {
-            return Err(errors::CustomErrors::InvalidStartDate);
-        }
+        events::publish_withdrawal_buckets_changed_event(&e, &buckets);
 
-        if &params.cancellable_date > &params.end_date {
-            return Err(errors::CustomErrors::InvalidCancellableDate);
-        }
+        Ok(())
+    }
 
-        if &params.cliff_date < &params.start_date || &params.cliff_date > &params.end_date {
-            return Err(errors::CustomErrors::InvalidCliffDate);
-        }
+    /// Returns the thresholds of the withdrawal buckets, none until the admin sets them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let buckets = fluxity_client::get_withdrawal_buckets();
+    /// ```
+    fn get_withdrawal_buckets(e: Env) -> Option<types::WithdrawalBuckets> {
+        storage::get_withdrawal_buckets(&e)
+    }
+
+    /// Sets the smallest amount a withdrawal can transfer, requires the admin's auth.
+    /// The last withdrawal of a cancelled or ended lockup can still be smaller, so
+    /// the receiver can always drain it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let min_withdraw = 1000000;
+    ///
+    /// fluxity_client::set_min_withdraw(&min_withdraw);
+    /// ```
+    fn set_min_withdraw(e: Env, min_withdraw: i128) -> Result<(), errors::CustomErrors> {
+        let admin = storage::get_admin(&e)?;
 
-        token::transfer_from(&e, &params.token, &params.sender, &params.amount);
+        admin.require_auth();
 
-        let id = storage::get_latest_lockup_id(&e);
-        let mut lockup: types::Lockup = params.into();
+        if min_withdraw < 0 {
+            return Err(errors::CustomErrors::InvalidAmount);
+        }
 
-        lockup.is_vesting = true;
+        storage::set_min_withdraw(&e, &min_withdraw);
 
-        storage::set_lockup(&e, id, &lockup);
-        storage::increment_latest_lockup_id(&e, &id);
-        events::publish_lockup_created_event(&e, id);
+        events::publish_min_withdraw_changed_event(&e, min_withdraw);
 
-        Ok(id)
+        Ok(())
     }
 
-    /// Increases the duration and the amount of a lockup
+    /// Returns the smallest amount a withdrawal can transfer, 0 until the admin sets it
     ///
     /// # Examples
     ///
     /// ```
-    /// let lockup_id = 56;
-    /// let adding_amount = 700000000;
+    /// let min_withdraw = fluxity_client::get_min_withdraw();
+    /// ```
+    fn get_min_withdraw(e: Env) -> i128 {
+        storage::get_min_withdraw(&e)
+    }
+
+    /// Returns the id of the latest created lockup
+    ///
+    /// Lockup ids start at 1, so 0 is returned when no lockup has been created yet.
+    /// Contracts which created lockups before that also have a lockup 0
+    ///
+    /// # Examples
     ///
-    /// fluxity_client::topup_lockup(lockup_id, adding_amount);
     /// ```
-    fn topup_lockup(e: Env, id: u64, adding_amount: i128) -> Result<i128, errors::CustomErrors> {
-        let mut lockup = get_lockup_by_id(&e, &id).unwrap();
+    /// let id = fluxity_client::get_latest_stream_id();
+    /// ```
+    fn get_latest_lockup_id(e: Env) -> u64 {
+        storage::get_latest_lockup_id(&e)
+    }
 
-        lockup.sender.require_auth();
+    /// Returns the number of lockups which aren't settled yet, a lockup counts from
+    /// its creation until nothing is left to be withdrawn from it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let count = fluxity_client::get_active_count();
+    /// ```
+    fn get_active_count(e: Env) -> u64 {
+        storage::get_active_count(&e)
+    }
 
-        if lockup.is_cancelled {
-            return Err(errors::CustomErrors::LockupIsCanceled);
-        }
+    /// Returns a lockup by id
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_lockup(&stream_id);
+    /// ```
+    fn get_lockup(e: Env, id: u64) -> Result<types::Lockup, errors::CustomErrors> {
+        storage::get_lockup_by_id(&e, &id)
+    }
 
-        let current_date = e.ledger().timestamp();
+    /// Rewrites a lockup stored in an older layout in the current one and counts what
+    /// it holds in the locked balance. Old entries are already upgraded when read
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::migrate_lockup(&lockup_id);
+    /// ```
+    fn migrate_lockup(e: Env, id: u64) -> Result<(), errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
 
-        if lockup.end_date < current_date {
-            return Err(errors::CustomErrors::LockupAlreadySettled);
-        }
+        storage::set_lockup(&e, id, &lockup);
 
-        let additional_duration = calculate_additional_time(&lockup, adding_amount);
+        Ok(())
+    }
 
-        if lockup.cancelled_date == lockup.end_date {
-            lockup.cancellable_date = lockup.cancellable_date + additional_duration;
+    /// Rewrites the next `limit` legacy lockups in the current layout, counting them in
+    /// the locked balance, and returns how many legacy ids are left to walk. At most 20
+    /// ids are walked per call, `sweep_token` sweeps nothing until none are left
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let remaining = fluxity_client::migrate_lockups(&20);
+    /// ```
+    fn migrate_lockups(e: Env, limit: u32) -> u64 {
+        let count = storage::get_legacy_lockup_count(&e);
+        let cursor = storage::get_legacy_migration_cursor(&e);
+        let end = count.min(cursor + limit.min(MAX_BATCH_SIZE) as u64);
+
+        for id in cursor..end {
+            if let Some(lockup) = storage::try_get_lockup(&e, id) {
+                storage::set_lockup(&e, id, &lockup);
+            }
         }
 
-        if lockup.cliff_date == lockup.end_date {
-            lockup.cliff_date = lockup.cliff_date + additional_duration;
+        if end > cursor {
+            storage::set_legacy_migration_cursor(&e, &end);
         }
 
-        lockup.amount = lockup.amount + adding_amount;
-        lockup.end_date = lockup.end_date + additional_duration;
-
-        storage::set_lockup(&e, id, &lockup);
+        count - end
+    }
 
-        events::publish_lockup_topup_event(&e, id);
+    /// Returns the input a lockup was created with, rebuilt from the stored lockup
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_lockup_input(&lockup_id);
+    /// ```
+    fn get_lockup_input(e: Env, id: u64) -> Result<types::LockupInput, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
 
-        Ok(lockup.amount)
+        Ok(lockup.into())
     }
+
+    /// Returns the XDR encoding of a lockup, so clients can cache it offline and
+    /// decode it back with `Lockup::from_xdr`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// let bytes = fluxity_client::export_lockup(&lockup_id);
+    /// let lockup = Lockup::from_xdr(&env, &bytes);
+    /// ```
+    fn export_lockup(e: Env, id: u64) -> Result<Bytes, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        Ok(lockup.to_xdr(&e))
+    }
+
+    /// Returns the core fields of a lockup as a flat tuple, in order: sender, receiver,
+    /// token, amount, withdrawn, start date, end date, cliff date, cancellable date,
+    /// is cancelled and is vesting
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_lockup_flat(&lockup_id);
+    /// ```
+    fn get_lockup_flat(e: Env, id: u64) -> Result<types::FlatLockup, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        Ok(lockup.into())
+    }
+
+    /// Returns the amount of a lockup along with the decimals of its token, so
+    /// clients can format it without calling the token
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// let (amount, decimals) = fluxity_client::get_lockup_amount_scaled(&lockup_id);
+    /// ```
+    fn get_lockup_amount_scaled(e: Env, id: u64) -> Result<(i128, u32), errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        Ok((lockup.amount, token::decimals(&e, &lockup.token)))
+    }
+
+    /// Returns many lockups at once, in the order of the ids. Missing lockups are
+    /// returned as `None` instead of failing the whole call
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ids = vec![&env, 20, 21, 22];
+    ///
+    /// fluxity_client::get_lockups(&ids);
+    /// ```
+    fn get_lockups(
+        e: Env,
+        ids: Vec<u64>,
+    ) -> Result<Vec<Option<types::Lockup>>, errors::CustomErrors> {
+        if ids.len() > MAX_BATCH_SIZE {
+            return Err(errors::CustomErrors::BatchTooLarge);
+        }
+
+        let mut lockups = vec![&e];
+
+        for id in ids.iter() {
+            lockups.push_back(storage::try_get_lockup(&e, id));
+        }
+
+        Ok(lockups)
+    }
+
+    /// Returns the id of the lockup created with the given external reference
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let external_ref = BytesN::from_array(&env, &[1; 32]);
+    ///
+    /// fluxity_client::get_lockup_by_external_ref(&external_ref);
+    /// ```
+    fn get_lockup_by_external_ref(
+        e: Env,
+        external_ref: BytesN<32>,
+    ) -> Result<u64, errors::CustomErrors> {
+        match storage::get_lockup_id_by_external_ref(&e, &external_ref) {
+            None => Err(errors::CustomErrors::LockupNotFound),
+            Some(id) => Ok(id),
+        }
+    }
+
+    /// Returns whether a lockup is a linear stream or a vesting
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_lockup_kind(&lockup_id);
+    /// ```
+    fn get_lockup_kind(e: Env, id: u64) -> Result<types::LockupKind, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        if !lockup.tranches.is_empty() {
+            return Ok(types::LockupKind::Tranched);
+        }
+
+        if lockup.is_vesting {
+            return Ok(types::LockupKind::Vesting);
+        }
+
+        Ok(types::LockupKind::Linear)
+    }
+
+    /// Returns true if a lockup has a cliff, which is when its cliff date is after
+    /// its start date
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::has_cliff(&lockup_id);
+    /// ```
+    fn has_cliff(e: Env, id: u64) -> Result<bool, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        Ok(lockup.cliff_date > lockup.start_date)
+    }
+
+    /// Returns the status of a lockup at the current time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_lockup_status(&lockup_id);
+    /// ```
+    fn get_lockup_status(e: Env, id: u64) -> Result<types::LockupStatus, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        Ok(utils::get_lockup_status(
+            &lockup,
+            current_date_for(&e, &lockup),
+        ))
+    }
+
+    /// Returns the number of seconds elapsed since a lockup was cancelled, estimated
+    /// from the ledger close time for lockups following the ledger sequence
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_time_since_cancel(&lockup_id);
+    /// ```
+    fn get_time_since_cancel(e: Env, id: u64) -> Result<u64, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        if !lockup.is_cancelled {
+            return Err(errors::CustomErrors::LockupNotCancelled);
+        }
+
+        Ok(span_in_seconds(
+            lockup.use_ledger_seq,
+            current_date_for(&e, &lockup) - lockup.cancelled_date,
+        ))
+    }
+
+    /// Returns true if nothing is left to be withdrawn from the lockup, either because
+    /// it's cancelled and drained or because it's ended and fully withdrawn. This is
+    /// the same definition `finalize_if_settled` emits the settled event with
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::is_lockup_settled(&lockup_id);
+    /// ```
+    fn is_lockup_settled(e: Env, id: u64) -> Result<bool, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        Ok(utils::is_settled(&lockup, current_date_for(&e, &lockup)))
+    }
+
+    /// Emits the settled event if nothing is left to be withdrawn from the lockup,
+    /// returns whether the lockup is settled. Reads like `get_lockup_status` never
+    /// emit anything
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::finalize_if_settled(&lockup_id);
+    /// ```
+    fn finalize_if_settled(e: Env, id: u64) -> Result<bool, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        if !utils::is_settled(&lockup, current_date_for(&e, &lockup)) {
+            return Ok(false);
+        }
+
+        events::publish_lockup_settled_event(&e, id);
+
+        Ok(true)
+    }
+
+    /// Closes an ended lockup by pushing what's left to the receiver, returns the
+    /// pushed amount. Ended lockups can't be cancelled since everything is vested,
+    /// this lets the sender settle them without waiting for the receiver to withdraw
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::finalize_lockup(&lockup_id);
+    /// ```
+    fn finalize_lockup(e: Env, id: u64) -> Result<i128, errors::CustomErrors> {
+        let mut lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        lockup.sender.require_auth();
+
+        ensure_not_group(&e, id)?;
+
+        if lockup.is_cancelled {
+            return Err(errors::CustomErrors::LockupIsCanceled);
+        }
+
+        let current_date = current_date_for(&e, &lockup);
+
+        if current_date < lockup.end_date {
+            return Err(errors::CustomErrors::LockupNotEndedYet);
+        }
+
+        let remaining = utils::calculate_withdrawable(&lockup, current_date)?;
+
+        if remaining > 0 {
+            lockup.withdrawn = add_withdrawn(&lockup, remaining)?;
+
+            storage::set_lockup(&e, id, &lockup);
+            track_settlement(&e, &lockup);
+            storage::decrease_locked_balance(&e, &lockup.token, &remaining);
+
+            token::transfer(
+                &e,
+                &lockup.token,
+                &payout_address(&e, id, &lockup),
+                &remaining,
+            );
+
+            publish_withdrawn_events(&e, id, &lockup, remaining);
+        }
+
+        if utils::is_settled(&lockup, current_date) {
+            events::publish_lockup_settled_event(&e, id);
+        }
+
+        Ok(remaining)
+    }
+
+    /// Sends the funds the receiver never withdrew back to the sender once the
+    /// dormancy period has passed since the end date, requires the sender's auth
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::reclaim_dormant(&lockup_id);
+    /// ```
+    fn reclaim_dormant(e: Env, id: u64) -> Result<i128, errors::CustomErrors> {
+        let mut lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        lockup.sender.require_auth();
+
+        let current_date = current_date_for(&e, &lockup);
+        let since_end = current_date.saturating_sub(lockup.end_date);
+
+        if span_in_seconds(lockup.use_ledger_seq, since_end) <= Self::get_dormancy_period(e.clone())
+        {
+            return Err(errors::CustomErrors::LockupNotDormant);
+        }
+
+        let reclaimed = utils::calculate_withdrawable(&lockup, current_date)?;
+
+        if reclaimed == 0 {
+            return Err(errors::CustomErrors::LockupAlreadySettled);
+        }
+
+        lockup.refunded = lockup
+            .refunded
+            .checked_add(reclaimed)
+            .ok_or(errors::CustomErrors::AmountOverflows)?;
+
+        storage::set_lockup(&e, id, &lockup);
+        track_settlement(&e, &lockup);
+        storage::decrease_locked_balance(&e, &lockup.token, &reclaimed);
+
+        token::transfer(&e, &lockup.token, &lockup.sender, &reclaimed);
+
+        events::publish_lockup_reclaimed_event(&e, id, &lockup.sender, reclaimed);
+
+        if utils::is_settled(&lockup, current_date) {
+            events::publish_lockup_settled_event(&e, id);
+        }
+
+        Ok(reclaimed)
+    }
+
+    /// Returns the amount the receiver can withdraw from a lockup at the current time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_withdrawable(&lockup_id);
+    /// ```
+    fn get_withdrawable(e: Env, id: u64) -> Result<i128, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        utils::calculate_withdrawable(&lockup, current_date_for(&e, &lockup))
+    }
+
+    /// Returns the amount the receiver could withdraw from a lockup at a timestamp
+    /// if nothing else is withdrawn until then
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let timestamp = 1710000000;
+    ///
+    /// fluxity_client::get_withdrawable_at(&lockup_id, &timestamp);
+    /// ```
+    fn get_withdrawable_at(e: Env, id: u64, timestamp: u64) -> Result<i128, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        utils::calculate_withdrawable(&lockup, timestamp)
+    }
+
+    /// Returns the total amount withdrawn from a lockup over its life
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_lockup_withdrawn(&lockup_id);
+    /// ```
+    fn get_lockup_withdrawn(e: Env, id: u64) -> Result<i128, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        Ok(lockup.withdrawn)
+    }
+
+    /// Returns the amount vested by a lockup at any past or future timestamp, which
+    /// is clamped to the lockup's span and to its cancellation date
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let timestamp = 1710000000;
+    ///
+    /// fluxity_client::get_vested_at(&lockup_id, &timestamp);
+    /// ```
+    fn get_vested_at(e: Env, id: u64, timestamp: u64) -> Result<i128, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+        let date = timestamp.min(utils::effective_date(&lockup, timestamp));
+
+        Ok(utils::calculate_lockup_amounts(&lockup, date)?.receiver_amount)
+    }
+
+    /// Returns the next date something unlocks in a lockup and the amount unlocking
+    /// then. Streams unlock continuously, so the current date and the rate of
+    /// `get_lockup_rate` are returned for them, and nothing for reversed streams
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// let (date, amount) = fluxity_client::get_next_unlock(&lockup_id);
+    /// ```
+    fn get_next_unlock(e: Env, id: u64) -> Result<(u64, i128), errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        utils::calculate_next_unlock(&lockup, current_date_for(&e, &lockup))
+    }
+
+    /// Returns the amount a lockup unlocks per second, or per ledger for lockups
+    /// following the ledger sequence. For vestings it's averaged over the whole
+    /// duration, and it's negative for reversed streams
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_lockup_rate(&lockup_id);
+    /// ```
+    fn get_lockup_rate(e: Env, id: u64) -> Result<i128, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        utils::calculate_rate_per_unit(&lockup)
+    }
+
+    /// Returns a lockup alongside its computed fields (status, vested, withdrawable
+    /// and progress in basis points) in a single call
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_lockup_detail(&lockup_id);
+    /// ```
+    fn get_lockup_detail(e: Env, id: u64) -> Result<types::LockupDetail, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        lockup_detail(&e, lockup)
+    }
+
+    /// Returns the details of the lockups starting at the `cursor` id, at most `limit`
+    /// of them and never more than 10, along with the cursor of the next page. The
+    /// next cursor is 0 once the last lockup has been listed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (details, cursor) = fluxity_client::list_lockups_detail(&0, &10);
+    /// let (details, cursor) = fluxity_client::list_lockups_detail(&cursor, &10);
+    /// ```
+    fn list_lockups_detail(e: Env, cursor: u64, limit: u32) -> (Vec<types::LockupDetail>, u64) {
+        let mut details = vec![&e];
+        let latest_id = storage::get_latest_lockup_id(&e);
+        let limit = limit.min(MAX_DETAIL_PAGE_SIZE);
+
+        let mut id = cursor;
+
+        while id <= latest_id && details.len() < limit {
+            let detail = storage::get_lockup_by_id(&e, &id).and_then(|l| lockup_detail(&e, l));
+
+            if let Ok(detail) = detail {
+                details.push_back(detail);
+            }
+
+            id += 1;
+        }
+
+        let next_cursor = if id > latest_id { 0 } else { id };
+
+        (details, next_cursor)
+    }
+
+    /// Returns the ids of the lockups in the given status starting at the `cursor` id,
+    /// at most `limit` of them and never more than 100, along with the cursor of the
+    /// next page. Each call reads at most 100 lockups whatever their status, so a page
+    /// can come back short or empty before the end, which the next cursor being 0 marks
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let (ids, cursor) = fluxity_client::list_lockups_by_status(&LockupStatus::Active, &0, &50);
+    /// let (ids, cursor) = fluxity_client::list_lockups_by_status(&LockupStatus::Active, &cursor, &50);
+    /// ```
+    fn list_lockups_by_status(
+        e: Env,
+        status: types::LockupStatus,
+        cursor: u64,
+        limit: u32,
+    ) -> (Vec<u64>, u64) {
+        let latest_id = storage::get_latest_lockup_id(&e);
+        let limit = limit.min(MAX_RANGE_LIMIT);
+        let mut ids = vec![&e];
+        let mut scanned = 0;
+
+        let mut id = cursor;
+
+        while id <= latest_id && ids.len() < limit && scanned < MAX_THRESHOLD_SCAN {
+            if let Ok(lockup) = storage::get_lockup_by_id(&e, &id) {
+                if utils::get_lockup_status(&lockup, current_date_for(&e, &lockup)) == status {
+                    ids.push_back(id);
+                }
+            }
+
+            scanned += 1;
+            id += 1;
+        }
+
+        let next_cursor = if id > latest_id { 0 } else { id };
+
+        (ids, next_cursor)
+    }
+
+    /// Returns the ids of the receiver's lockups which currently have at least
+    /// `min_withdrawable` to withdraw, so bots can skip withdrawing dust. Pages start
+    /// at the `cursor` position of the receiver's index and hold at most `limit` ids,
+    /// each call scans at most 100 lockups and the next cursor is 0 at the end
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let receiver = Address::random(&env);
+    /// let min_withdrawable = 10000000;
+    ///
+    /// let (ids, cursor) = fluxity_client::get_lockups_over_threshold(&receiver, &min_withdrawable, &0, &50);
+    /// ```
+    fn get_lockups_over_threshold(
+        e: Env,
+        receiver: Address,
+        min_withdrawable: i128,
+        cursor: u32,
+        limit: u32,
+    ) -> (Vec<u64>, u32) {
+        let count = storage::get_receiver_lockup_count(&e, &receiver);
+        let limit = limit.min(MAX_RANGE_LIMIT);
+        let mut ids = vec![&e];
+        let mut position = cursor;
+        let page = storage::get_receiver_lockup_ids(&e, &receiver, cursor, MAX_THRESHOLD_SCAN);
+
+        for id in page.iter() {
+            if ids.len() >= limit {
+                break;
+            }
+
+            position += 1;
+
+            let Ok(lockup) = storage::get_lockup_by_id(&e, &id) else {
+                continue;
+            };
+
+            let current_date = current_date_for(&e, &lockup);
+            let withdrawable = utils::calculate_withdrawable(&lockup, current_date).unwrap_or(0);
+
+            if withdrawable > 0 && withdrawable >= min_withdrawable {
+                ids.push_back(id);
+            }
+        }
+
+        let next_cursor = if position >= count { 0 } else { position };
+
+        (ids, next_cursor)
+    }
+
+    /// Returns the ids of the sender's lockups which can be cancelled right now. Pages
+    /// start at the `cursor` position of the sender's index and hold at most `limit`
+    /// ids, each call scans at most 100 lockups and the next cursor is 0 at the end
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let sender = Address::random(&env);
+    ///
+    /// let (ids, cursor) = fluxity_client::get_cancellable_lockups(&sender, &0, &20);
+    /// ```
+    fn get_cancellable_lockups(
+        e: Env,
+        sender: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> (Vec<u64>, u32) {
+        let count = storage::get_sender_lockup_count(&e, &sender);
+        let limit = limit.min(MAX_RANGE_LIMIT);
+        let mut ids = vec![&e];
+        let mut position = cursor;
+        let page = storage::get_sender_lockup_ids(&e, &sender, cursor, MAX_THRESHOLD_SCAN);
+
+        for id in page.iter() {
+            if ids.len() >= limit {
+                break;
+            }
+
+            position += 1;
+
+            let Ok(lockup) = storage::get_lockup_by_id(&e, &id) else {
+                continue;
+            };
+
+            if utils::check_cancellable(&lockup, current_date_for(&e, &lockup)).is_ok() {
+                ids.push_back(id);
+            }
+        }
+
+        let next_cursor = if position >= count { 0 } else { position };
+
+        (ids, next_cursor)
+    }
+
+    /// Returns the ids of the lockups from the sender to the receiver. Pages start at
+    /// the `cursor` position of the sender's index and hold at most `limit` ids, each
+    /// call scans at most 100 lockups and the next cursor is 0 at the end
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let sender = Address::random(&env);
+    /// let receiver = Address::random(&env);
+    ///
+    /// let (ids, cursor) = fluxity_client::get_lockups_between_parties(&sender, &receiver, &0, &20);
+    /// ```
+    fn get_lockups_between_parties(
+        e: Env,
+        sender: Address,
+        receiver: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> (Vec<u64>, u32) {
+        let count = storage::get_sender_lockup_count(&e, &sender);
+        let limit = limit.min(MAX_RANGE_LIMIT);
+        let mut ids = vec![&e];
+        let mut position = cursor;
+        let page = storage::get_sender_lockup_ids(&e, &sender, cursor, MAX_THRESHOLD_SCAN);
+
+        for id in page.iter() {
+            if ids.len() >= limit {
+                break;
+            }
+
+            position += 1;
+
+            let Ok(lockup) = storage::get_lockup_by_id(&e, &id) else {
+                continue;
+            };
+
+            if lockup.receiver == receiver {
+                ids.push_back(id);
+            }
+        }
+
+        let next_cursor = if position >= count { 0 } else { position };
+
+        (ids, next_cursor)
+    }
+
+    /// Returns the ids of the lockups created between `from` and `to` (inclusive),
+    /// at most `limit` of them and never more than 100
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let from = 1700000000;
+    /// let to = 1710000000;
+    ///
+    /// fluxity_client::get_lockups_created_between(&from, &to, &50);
+    /// ```
+    fn get_lockups_created_between(e: Env, from: u64, to: u64, limit: u32) -> Vec<u64> {
+        let mut ids = vec![&e];
+        let latest_id = storage::get_latest_lockup_id(&e);
+
+        // Ids are assigned in creation order, so the creation dates are sorted by id
+        // and the first lockup of the range can be found with a binary search
+        let mut low = 0;
+        let mut high = latest_id + 1;
+
+        while low < high {
+            let middle = low + (high - low) / 2;
+
+            match storage::get_lockup_by_id(&e, &middle) {
+                Ok(lockup) if lockup.created_at < from => low = middle + 1,
+                _ => high = middle,
+            }
+        }
+
+        let limit = limit.min(MAX_RANGE_LIMIT);
+
+        for id in low..=latest_id {
+            if ids.len() >= limit {
+                break;
+            }
+
+            let Ok(lockup) = storage::get_lockup_by_id(&e, &id) else {
+                continue;
+            };
+
+            if lockup.created_at > to {
+                break;
+            }
+
+            // A missing lockup can stop the search before lockups created earlier
+            if lockup.created_at < from {
+                continue;
+            }
+
+            ids.push_back(id);
+        }
+
+        ids
+    }
+
+    /// Returns the `(date, cumulative unlocked amount)` steps of the vesting the params
+    /// would create, without creating anything. At most the first 100 steps are
+    /// returned, and invalid dates return no steps
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let params = VestingInput {
+    ///     sender: Address::random(&env),
+    ///     receiver: Address::random(&env),
+    ///     token: Address::random(&env),
+    ///     amount: 20000000,
+    ///     start_date: now,
+    ///     cancellable_date: now,
+    ///     cliff_date: now,
+    ///     end_date: now + 1000,
+    ///     rate: Rate::Daily
+    /// };
+    ///
+    /// fluxity_client::preview_vesting_schedule(&params);
+    /// ```
+    fn preview_vesting_schedule(e: Env, params: types::LockupInput) -> Vec<(u64, i128)> {
+        let mut schedule = vec![&e];
+
+        if params.start_date >= params.end_date
+            || params.cliff_date < params.start_date
+            || params.cliff_date > params.end_date
+        {
+            return schedule;
+        }
+
+        let mut lockup: types::Lockup = params.into();
+        lockup.is_vesting = true;
+
+        let mut date = lockup.start_date;
+
+        while date < lockup.end_date && schedule.len() < MAX_SCHEDULE_STEPS {
+            let Ok((next_date, _)) = utils::calculate_next_unlock(&lockup, date) else {
+                break;
+            };
+            let Ok(amounts) = utils::calculate_lockup_amounts(&lockup, next_date) else {
+                break;
+            };
+
+            schedule.push_back((next_date, amounts.receiver_amount));
+            date = next_date;
+        }
+
+        schedule
+    }
+
+    /// Creates an stream, with `use_default_rate` its rate is replaced by the default
+    /// rate when the admin has set one. The rate doesn't change how the amount accrues
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let params = StreamInput {
+    ///     sender: Address::random(&env),
+    ///     receiver: Address::random(&env),
+    ///     token: Address::random(&env),
+    ///     amount: 20000000,
+    ///     start_date: now,
+    ///     cancellable_date: now,
+    ///     cliff_date: now + 100,
+    ///     end_date: now + 1000,
+    ///     rate: Rate::Daily
+    /// };
+    ///
+    /// fluxity_client::create_stream(&params);
+    /// ```
+    fn create_stream(e: Env, params: types::LockupInput) -> Result<u64, errors::CustomErrors> {
+        let mut params = params;
+
+        if params.options.use_default_rate {
+            if let Some(rate) = storage::get_default_rate(&e) {
+                params.rate = rate;
+            }
+        }
+
+        create_lockup(&e, params, false, vec![&e])
+    }
+
+    /// Creates a stream of `per_period` for each period of the rate between the start
+    /// and the end dates, the amount of the params is replaced by the total. The
+    /// duration must be a whole number of periods so no remainder is left. The periods
+    /// are in seconds, so the dates can't be ledger sequences
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let params = StreamInput {
+    ///     sender: Address::random(&env),
+    ///     receiver: Address::random(&env),
+    ///     token: Address::random(&env),
+    ///     amount: 0,
+    ///     start_date: now,
+    ///     cancellable_date: now,
+    ///     cliff_date: now,
+    ///     end_date: now + 30 * 86400,
+    ///     rate: Rate::Daily
+    /// };
+    ///
+    /// fluxity_client::create_stream_by_rate(&params, &1000000000);
+    /// ```
+    fn create_stream_by_rate(
+        e: Env,
+        params: types::LockupInput,
+        per_period: i128,
+    ) -> Result<u64, errors::CustomErrors> {
+        if per_period <= 0 {
+            return Err(errors::CustomErrors::InvalidAmount);
+        }
+
+        if params.options.use_ledger_seq {
+            return Err(errors::CustomErrors::UnsupportedForLedgerSeq);
+        }
+
+        if params.start_date >= params.end_date {
+            return Err(errors::CustomErrors::InvalidStartDate);
+        }
+
+        let duration = params.end_date - params.start_date;
+
+        if !duration.is_multiple_of(params.rate as u64) {
+            return Err(errors::CustomErrors::InvalidDuration);
+        }
+
+        let periods = (duration / params.rate as u64) as i128;
+        let amount = per_period
+            .checked_mul(periods)
+            .ok_or(errors::CustomErrors::AmountOverflows)?;
+
+        create_lockup(&e, types::LockupInput { amount, ..params }, false, vec![&e])
+    }
+
+    /// Streams the amount to a group of receivers by shares. The group shares a single
+    /// stream held by the contract, each member withdraws their share of it through
+    /// `withdraw_group_share` and the rounding remainder goes to the last member. The
+    /// receiver of the params is ignored and the stream can't be tokenized. At most 20
+    /// receivers can be listed, returns the id of the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let params = StreamInput {
+    ///     sender: Address::random(&env),
+    ///     receiver: Address::random(&env),
+    ///     token: Address::random(&env),
+    ///     amount: 20000000,
+    ///     start_date: now,
+    ///     cancellable_date: now,
+    ///     cliff_date: now,
+    ///     end_date: now + 1000,
+    ///     rate: Rate::Daily
+    /// };
+    /// let receivers = vec![&env, (Address::random(&env), 3), (Address::random(&env), 1)];
+    ///
+    /// fluxity_client::create_group_stream(&params, &receivers);
+    /// ```
+    fn create_group_stream(
+        e: Env,
+        params: types::LockupInput,
+        receivers: Vec<(Address, u32)>,
+    ) -> Result<u64, errors::CustomErrors> {
+        if receivers.len() > MAX_BATCH_SIZE {
+            return Err(errors::CustomErrors::BatchTooLarge);
+        }
+
+        if params.options.tokenized {
+            return Err(errors::CustomErrors::UnsupportedForGroup);
+        }
+
+        if receivers.is_empty() || receivers.iter().any(|(_, shares)| shares == 0) {
+            return Err(errors::CustomErrors::InvalidAmount);
+        }
+
+        for (index, (receiver, _)) in receivers.iter().enumerate() {
+            let is_listed_before = receivers
+                .iter()
+                .take(index)
+                .any(|(listed, _)| listed == receiver);
+
+            if receiver == params.sender || is_listed_before {
+                return Err(errors::CustomErrors::InvalidReceiver);
+            }
+        }
+
+        let id = create_lockup(
+            &e,
+            types::LockupInput {
+                receiver: e.current_contract_address(),
+                ..params
+            },
+            false,
+            vec![&e],
+        )?;
+
+        storage::set_group_shares(&e, id, &receivers);
+
+        Ok(id)
+    }
+
+    /// Cancels a lockup, returns the amounts transferred to the sender and the receiver.
+    /// If `push_to_receiver` is false, the vested amount is not transferred and stays
+    /// claimable by the receiver through `withdraw_lockup`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::cancel_lockup(&lockup_id, &true);
+    /// ```
+    fn cancel_lockup(
+        e: Env,
+        id: u64,
+        push_to_receiver: bool,
+    ) -> Result<(i128, i128), errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id).unwrap();
+
+        lockup.sender.require_auth();
+
+        cancel(&e, id, lockup, push_to_receiver)
+    }
+
+    /// Same as `cancel_lockup`, but repeating a call with the same nonce is a no-op
+    /// returning the amounts of the first call, so clients can safely retry
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let nonce = 1;
+    ///
+    /// fluxity_client::cancel_lockup_with_nonce(&lockup_id, &true, &nonce);
+    /// ```
+    fn cancel_lockup_with_nonce(
+        e: Env,
+        id: u64,
+        push_to_receiver: bool,
+        nonce: u64,
+    ) -> Result<(i128, i128), errors::CustomErrors> {
+        if let Some(amounts) = storage::get_cancel_nonce_result(&e, id, nonce) {
+            return Ok(amounts);
+        }
+
+        let amounts = Self::cancel_lockup(e.clone(), id, push_to_receiver)?;
+
+        storage::record_cancel_nonce(&e, id, nonce, amounts);
+
+        Ok(amounts)
+    }
+
+    /// Cancels a lockup with a split both parties agreed on instead of the vested
+    /// one, requires the auth of the sender and the receiver. `to_receiver` is paid
+    /// out of what is left in the lockup and the rest goes back to the sender without
+    /// a cancellation fee. Returns the amounts transferred to the sender and the receiver
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let to_receiver = 5000000;
+    ///
+    /// fluxity_client::settle_lockup(&lockup_id, &to_receiver);
+    /// ```
+    fn settle_lockup(
+        e: Env,
+        id: u64,
+        to_receiver: i128,
+    ) -> Result<(i128, i128), errors::CustomErrors> {
+        let mut lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        ensure_not_group(&e, id)?;
+
+        lockup.sender.require_auth();
+        lockup.receiver.require_auth();
+
+        if lockup.is_cancelled {
+            return Err(errors::CustomErrors::LockupAlreadyCanceled);
+        }
+
+        let remaining = lockup.funded_amount - utils::paid_out(&lockup);
+
+        if remaining <= 0 {
+            return Err(errors::CustomErrors::LockupAlreadySettled);
+        }
+
+        if to_receiver < 0 || to_receiver > remaining {
+            return Err(errors::CustomErrors::InvalidAmount);
+        }
+
+        let to_sender = remaining - to_receiver;
+
+        // Only what the receiver gets counts as withdrawn, the sender's part is counted
+        // as refunded like a reclaim. Together they pay out the whole funded amount, so
+        // nothing is left to withdraw whatever was vested
+        lockup.is_cancelled = true;
+        lockup.cancelled_date = current_date_for(&e, &lockup);
+        lockup.withdrawn = add_withdrawn(&lockup, to_receiver)?;
+        lockup.refunded = lockup
+            .refunded
+            .checked_add(to_sender)
+            .ok_or(errors::CustomErrors::AmountOverflows)?;
+
+        storage::acquire_reentrancy_lock(&e)?;
+
+        storage::set_lockup(&e, id, &lockup);
+        track_settlement(&e, &lockup);
+        storage::decrease_locked_balance(&e, &lockup.token, &remaining);
+
+        if to_receiver > 0 {
+            token::transfer(
+                &e,
+                &lockup.token,
+                &payout_address(&e, id, &lockup),
+                &to_receiver,
+            );
+        }
+
+        if to_sender > 0 {
+            token::transfer(&e, &lockup.token, &lockup.sender, &to_sender);
+        }
+
+        storage::release_reentrancy_lock(&e);
+
+        events::publish_lockup_cancelled_event(&e, id, &lockup.sender);
+        events::publish_lockup_settled_event(&e, id);
+
+        Ok((to_sender, to_receiver))
+    }
+
+    /// Cancels a lockup and creates a stream with new terms in the same call, so the
+    /// receiver is never left without one. The vested amount is pushed to the receiver
+    /// and the refund reaches the sender before the new deposit is pulled, so the
+    /// sender only pays the difference but the allowance has to cover the new amount.
+    /// Returns the id of the new stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let new_params = StreamInput {
+    ///     sender: Address::random(&env),
+    ///     receiver: Address::random(&env),
+    ///     token: Address::random(&env),
+    ///     amount: 20000000,
+    ///     start_date: now,
+    ///     cancellable_date: now,
+    ///     cliff_date: now,
+    ///     end_date: now + 1000,
+    ///     rate: Rate::Daily
+    /// };
+    ///
+    /// fluxity_client::renew_stream(&lockup_id, &new_params);
+    /// ```
+    fn renew_stream(
+        e: Env,
+        id: u64,
+        new_params: types::LockupInput,
+    ) -> Result<u64, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        lockup.sender.require_auth();
+
+        if new_params.sender != lockup.sender {
+            return Err(errors::CustomErrors::LockupSenderMismatch);
+        }
+
+        cancel(&e, id, lockup, true)?;
+
+        create_lockup(&e, new_params, false, vec![&e])
+    }
+
+    /// Cancels many lockups of the same sender at once, pushing the vested amounts to
+    /// the receivers. If `strict` is true, the whole call reverts when a lockup can't
+    /// be cancelled, otherwise the lockup is skipped and marked as not cancelled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let sender = Address::random(&env);
+    /// let ids = vec![&env, 20, 21, 22];
+    ///
+    /// fluxity_client::cancel_lockups(&sender, &ids, &false);
+    /// ```
+    fn cancel_lockups(
+        e: Env,
+        sender: Address,
+        ids: Vec<u64>,
+        strict: bool,
+    ) -> Result<Vec<types::CancellationResult>, errors::CustomErrors> {
+        if ids.len() > MAX_BATCH_SIZE {
+            return Err(errors::CustomErrors::BatchTooLarge);
+        }
+
+        sender.require_auth();
+
+        let mut results = vec![&e];
+
+        for id in ids.iter() {
+            let result = storage::get_lockup_by_id(&e, &id).and_then(|lockup| {
+                if lockup.sender != sender {
+                    return Err(errors::CustomErrors::LockupSenderMismatch);
+                }
+
+                cancel(&e, id, lockup, true)
+            });
+
+            match result {
+                Ok((sender_amount, receiver_amount)) => {
+                    results.push_back(types::CancellationResult {
+                        id,
+                        cancelled: true,
+                        sender_amount,
+                        receiver_amount,
+                    });
+                }
+                Err(error) if strict => return Err(error),
+                Err(_) => {
+                    results.push_back(types::CancellationResult {
+                        id,
+                        cancelled: false,
+                        sender_amount: 0,
+                        receiver_amount: 0,
+                    });
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Returns true if the address can cancel the lockup at the current time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let who = Address::random(&env);
+    ///
+    /// fluxity_client::can_cancel(&lockup_id, &who);
+    /// ```
+    fn can_cancel(e: Env, id: u64, who: Address) -> bool {
+        let Ok(lockup) = storage::get_lockup_by_id(&e, &id) else {
+            return false;
+        };
+
+        lockup.sender == who
+            && utils::check_cancellable(&lockup, current_date_for(&e, &lockup)).is_ok()
+    }
+
+    /// Returns the amount the sender would get back if the lockup was cancelled at
+    /// the current time net of the cancellation fee, or 0 if it can't be cancelled now
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_refundable_to_sender(&lockup_id);
+    /// ```
+    fn get_refundable_to_sender(e: Env, id: u64) -> Result<i128, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+        let current_date = current_date_for(&e, &lockup);
+
+        if utils::check_cancellable(&lockup, current_date).is_err() {
+            return Ok(0);
+        }
+
+        let (sender_amount, _) = cancellation_amounts(&lockup, current_date)?;
+
+        Ok(sender_amount - calculate_cancel_fee(&e, sender_amount)?)
+    }
+
+    /// Withdraws from a lockup, anyone call call this function even for others. The
+    /// tokens go to the default destination of the lockup if the receiver set one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let amount_to_withdraw = 30000000 // Represents 3 in a 7-decimal token
+    ///
+    /// fluxity_client::withdraw_lockup(&stream_id, &amount_to_withdraw);
+    /// ```
+    fn withdraw_lockup(e: Env, id: u64, amount: i128) -> Result<i128, errors::CustomErrors> {
+        let mut lockup = storage::get_lockup_by_id(&e, &id).unwrap();
+
+        ensure_not_group(&e, id)?;
+
+        let amount_to_transfer = withdrawal_amount(&e, id, &lockup, amount)?;
+
+        if amount_to_transfer == 0 {
+            return Ok(0);
+        }
+
+        lockup.withdrawn = add_withdrawn(&lockup, amount_to_transfer)?;
+
+        storage::acquire_reentrancy_lock(&e)?;
+
+        storage::set_lockup(&e, id, &lockup);
+        track_settlement(&e, &lockup);
+        storage::decrease_locked_balance(&e, &lockup.token, &amount_to_transfer);
+
+        token::transfer(
+            &e,
+            &lockup.token,
+            &payout_address(&e, id, &lockup),
+            &amount_to_transfer,
+        );
+
+        storage::release_reentrancy_lock(&e);
+
+        publish_withdrawn_events(&e, id, &lockup, amount_to_transfer);
+
+        Ok(amount_to_transfer)
+    }
+
+    /// Same as `withdraw_lockup`, but repeating a call with the same nonce is a no-op
+    /// returning the amount of the first call, so clients can safely retry. Only the
+    /// latest 10 nonces of a lockup are remembered
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let nonce = 1;
+    ///
+    /// fluxity_client::withdraw_lockup_with_nonce(&lockup_id, &0, &nonce);
+    /// ```
+    fn withdraw_lockup_with_nonce(
+        e: Env,
+        id: u64,
+        amount: i128,
+        nonce: u64,
+    ) -> Result<i128, errors::CustomErrors> {
+        if let Some(withdrawn) = storage::get_withdraw_nonce_result(&e, id, nonce) {
+            return Ok(withdrawn);
+        }
+
+        let withdrawn = Self::withdraw_lockup(e.clone(), id, amount)?;
+
+        storage::record_withdraw_nonce(&e, id, nonce, withdrawn, MAX_NONCES_PER_LOCKUP);
+
+        Ok(withdrawn)
+    }
+
+    /// Withdraws everything withdrawable from the listed lockups of the receiver and
+    /// returns the total, lockups of other receivers or with nothing to withdraw are
+    /// skipped. At most 20 lockups can be listed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let receiver = Address::random(&env);
+    /// let ids = vec![&env, 20, 21, 22];
+    ///
+    /// fluxity_client::withdraw_all(&receiver, &ids);
+    /// ```
+    fn withdraw_all(
+        e: Env,
+        receiver: Address,
+        ids: Vec<u64>,
+    ) -> Result<i128, errors::CustomErrors> {
+        if ids.len() > MAX_BATCH_SIZE {
+            return Err(errors::CustomErrors::BatchTooLarge);
+        }
+
+        receiver.require_auth();
+
+        let mut total = 0;
+
+        for id in ids.iter() {
+            let Ok(lockup) = storage::get_lockup_by_id(&e, &id) else {
+                continue;
+            };
+
+            if lockup.receiver != receiver
+                || !matches!(withdrawal_amount(&e, id, &lockup, 0), Ok(1..))
+            {
+                continue;
+            }
+
+            total += Self::withdraw_lockup(e.clone(), id, 0)?;
+        }
+
+        Ok(total)
+    }
+
+    /// Withdraws the share of a group member from a group stream, anyone can call this
+    /// function even for others. An `amount` of 0 means everything the member can
+    /// withdraw, and the minimum withdrawal applies to what the member withdraws
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let member = Address::random(&env);
+    ///
+    /// fluxity_client::withdraw_group_share(&lockup_id, &member, &0);
+    /// ```
+    fn withdraw_group_share(
+        e: Env,
+        id: u64,
+        member: Address,
+        amount: i128,
+    ) -> Result<i128, errors::CustomErrors> {
+        let mut lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        let shares =
+            storage::get_group_shares(&e, id).ok_or(errors::CustomErrors::NotGroupMember)?;
+
+        if amount < 0 {
+            return Err(errors::CustomErrors::AmountUnderflows);
+        }
+
+        // The minimum applies to what the member withdraws, not to the whole group
+        let (available, is_final) = validate_withdrawal(&e, id, &lockup, 0)?;
+        let received = lockup
+            .withdrawn
+            .checked_add(available)
+            .ok_or(errors::CustomErrors::AmountOverflows)?;
+        let member_withdrawn = storage::get_group_withdrawn(&e, id, &member);
+
+        // The last member gets the rounding remainder, and the shares together never
+        // pay out more than the stream has
+        let withdrawable = utils::calculate_group_share(received, &shares, &member)?
+            .ok_or(errors::CustomErrors::NotGroupMember)?
+            .saturating_sub(member_withdrawn)
+            .clamp(0, available);
+
+        if withdrawable < amount {
+            return Err(errors::CustomErrors::SpecifiedAmountIsGreaterThanWithdrawable);
+        }
+
+        let amount_to_transfer = if amount == 0 { withdrawable } else { amount };
+
+        if amount_to_transfer == 0 {
+            return Ok(0);
+        }
+
+        ensure_min_withdraw(
+            &e,
+            amount_to_transfer,
+            is_final && amount_to_transfer == withdrawable,
+        )?;
+
+        lockup.withdrawn = add_withdrawn(&lockup, amount_to_transfer)?;
+
+        storage::acquire_reentrancy_lock(&e)?;
+
+        storage::set_lockup(&e, id, &lockup);
+        storage::set_group_withdrawn(&e, id, &member, &(member_withdrawn + amount_to_transfer));
+        track_settlement(&e, &lockup);
+        storage::decrease_locked_balance(&e, &lockup.token, &amount_to_transfer);
+
+        token::transfer(&e, &lockup.token, &member, &amount_to_transfer);
+
+        storage::release_reentrancy_lock(&e);
+
+        publish_withdrawn_events(&e, id, &lockup, amount_to_transfer);
+
+        Ok(amount_to_transfer)
+    }
+
+    /// Returns the members of a group stream and their shares, `None` if the lockup
+    /// isn't a group stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_group_shares(&lockup_id);
+    /// ```
+    fn get_group_shares(e: Env, id: u64) -> Option<Vec<(Address, u32)>> {
+        storage::get_group_shares(&e, id)
+    }
+
+    /// Returns what a member of a group stream has withdrawn so far
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let member = Address::random(&env);
+    ///
+    /// fluxity_client::get_group_withdrawn(&lockup_id, &member);
+    /// ```
+    fn get_group_withdrawn(e: Env, id: u64, member: Address) -> i128 {
+        storage::get_group_withdrawn(&e, id, &member)
+    }
+
+    /// Returns the amount `withdraw_lockup` would transfer to the receiver right now,
+    /// without transferring anything. An `amount` of 0 means everything withdrawable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::simulate_withdraw(&lockup_id, &0);
+    /// ```
+    fn simulate_withdraw(e: Env, id: u64, amount: i128) -> Result<i128, errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        ensure_not_group(&e, id)?;
+
+        withdrawal_amount(&e, id, &lockup, amount)
+    }
+
+    /// Returns true if the address is the receiver of the lockup and it has something
+    /// to withdraw at the current time (started, past the cliff and the withdraw unlock
+    /// date and not fully withdrawn)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let who = Address::random(&env);
+    ///
+    /// fluxity_client::can_withdraw(&lockup_id, &who);
+    /// ```
+    fn can_withdraw(e: Env, id: u64, who: Address) -> bool {
+        let Ok(lockup) = storage::get_lockup_by_id(&e, &id) else {
+            return false;
+        };
+
+        let current_date = current_date_for(&e, &lockup);
+
+        lockup.receiver == who
+            && current_date >= lockup.withdraw_unlock_date
+            && is_released(&e, id, &lockup)
+            && utils::calculate_withdrawable(&lockup, current_date).unwrap_or(0) > 0
+    }
+
+    /// Sets the address everything paid to the receiver of a lockup goes to instead,
+    /// withdrawals as well as the amounts pushed on cancellation, finalization and
+    /// settlement. Only the receiver can set it, it's cleared when the lockup is
+    /// transferred
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let destination = Address::random(&env);
+    ///
+    /// fluxity_client::set_default_destination(&lockup_id, &destination);
+    /// ```
+    fn set_default_destination(
+        e: Env,
+        id: u64,
+        destination: Address,
+    ) -> Result<(), errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        lockup.receiver.require_auth();
+
+        storage::set_default_destination(&e, id, &destination);
+
+        events::publish_lockup_destination_event(&e, id);
+
+        Ok(())
+    }
+
+    /// Clears the default destination of a lockup, so withdrawals are paid to the
+    /// receiver again
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::clear_default_destination(&lockup_id);
+    /// ```
+    fn clear_default_destination(e: Env, id: u64) -> Result<(), errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        lockup.receiver.require_auth();
+
+        storage::remove_default_destination(&e, id);
+
+        events::publish_lockup_destination_event(&e, id);
+
+        Ok(())
+    }
+
+    /// Returns the default destination of a lockup, if the receiver set one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// fluxity_client::get_default_destination(&lockup_id);
+    /// ```
+    fn get_default_destination(e: Env, id: u64) -> Option<Address> {
+        storage::get_default_destination(&e, id)
+    }
+
+    /// Transfers a lockup to a new receiver, who becomes entitled to everything not
+    /// withdrawn yet. The new receiver can't be the sender of the lockup, and the
+    /// sender can opt out of transfers on creation. Tokenized lockups follow their
+    /// NFT, so the new receiver has to own it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let new_receiver = Address::random(&env);
+    ///
+    /// fluxity_client::transfer_lockup_receiver(&lockup_id, &new_receiver);
+    /// ```
+    fn transfer_lockup_receiver(
+        e: Env,
+        id: u64,
+        new_receiver: Address,
+    ) -> Result<(), errors::CustomErrors> {
+        let mut lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        if !lockup.receiver_transferable {
+            return Err(errors::CustomErrors::TransferNotAllowed);
+        }
+
+        match &lockup.position {
+            types::Position::None => lockup.receiver.require_auth(),
+            types::Position::Tokenized(nft, token_id) => {
+                if position_nft::owner_of(&e, nft, *token_id) != new_receiver {
+                    return Err(errors::CustomErrors::PositionOwnerMismatch);
+                }
+            }
+        }
+
+        if new_receiver == lockup.sender {
+            return Err(errors::CustomErrors::InvalidReceiver);
+        }
+
+        storage::remove_receiver_lockup_id(&e, &lockup.receiver, id);
+        storage::add_receiver_lockup_id(&e, &new_receiver, id);
+
+        lockup.receiver = new_receiver;
+
+        storage::set_lockup(&e, id, &lockup);
+        storage::remove_default_destination(&e, id);
+
+        events::publish_lockup_transferred_event(&e, id);
+
+        Ok(())
+    }
+
+    /// Creates a vesting stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let params = VestingInput {
+    ///     sender: Address::random(&env),
+    ///     receiver: Address::random(&env),
+    ///     token: Address::random(&env),
+    ///     amount: 20000000,
+    ///     start_date: now,
+    ///     cancellable_date: now,
+    ///     cliff_date: now + 100,
+    ///     end_date: now + 1000,
+    ///     rate: Rate::Daily
+    /// };
+    ///
+    /// fluxity_client::create_vesting(&params);
+    /// ```
+    fn create_vesting(e: Env, params: types::LockupInput) -> Result<u64, errors::CustomErrors> {
+        create_lockup(&e, params, true, vec![&e])
+    }
+
+    /// Creates a vesting which unlocks each tranche in full at its date, the
+    /// tranches must be sorted by date and sum up to the amount (net of any
+    /// skimmed creation fee)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let params = VestingInput {
+    ///     sender: Address::random(&env),
+    ///     receiver: Address::random(&env),
+    ///     token: Address::random(&env),
+    ///     amount: 20000000,
+    ///     start_date: now,
+    ///     cancellable_date: now,
+    ///     cliff_date: now,
+    ///     end_date: now + 1000,
+    ///     rate: Rate::Daily
+    /// };
+    /// let tranches = vec![&env, (now + 500, 10000000), (now + 1000, 10000000)];
+    ///
+    /// fluxity_client::create_tranched_vesting(&params, &tranches);
+    /// ```
+    fn create_tranched_vesting(
+        e: Env,
+        params: types::LockupInput,
+        tranches: Vec<(u64, i128)>,
+    ) -> Result<u64, errors::CustomErrors> {
+        if tranches.is_empty() {
+            return Err(errors::CustomErrors::InvalidTranches);
+        }
+
+        create_lockup(&e, params, true, tranches)
+    }
+
+    /// Increases the duration and the amount of a stream, the added amount is not
+    /// transferred and has to be funded using `fund_lockup` before it can be withdrawn.
+    /// Vestings can't be topped up
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 56;
+    /// let adding_amount = 700000000;
+    ///
+    /// fluxity_client::topup_lockup(lockup_id, adding_amount);
+    /// ```
+    fn topup_lockup(e: Env, id: u64, adding_amount: i128) -> Result<i128, errors::CustomErrors> {
+        let mut lockup = get_lockup_by_id(&e, &id).unwrap();
+
+        lockup.sender.require_auth();
+
+        if lockup.is_cancelled {
+            return Err(errors::CustomErrors::LockupIsCanceled);
+        }
+
+        // The added duration is derived from the linear rate, which doesn't hold
+        // for period or tranche based vestings
+        if lockup.is_vesting {
+            return Err(errors::CustomErrors::UnsupportedForVesting);
+        }
+
+        let current_date = current_date_for(&e, &lockup);
+
+        if lockup.end_date < current_date {
+            return Err(errors::CustomErrors::LockupAlreadySettled);
+        }
+
+        let additional_duration = calculate_additional_time(&lockup, adding_amount);
+
+        if lockup.cancelled_date == lockup.end_date {
+            lockup.cancellable_date += additional_duration;
+        }
+
+        if lockup.cliff_date == lockup.end_date {
+            lockup.cliff_date += additional_duration;
+        }
+
+        lockup.amount += adding_amount;
+        lockup.end_date += additional_duration;
+
+        storage::set_lockup(&e, id, &lockup);
+
+        events::publish_lockup_topup_event(&e, id, adding_amount, lockup.amount);
+
+        Ok(lockup.amount)
+    }
+
+    /// Moves the end date of a lockup closer so the remaining amount vests faster,
+    /// the amount of the lockup stays the same. Tranches, the cliff and the cancellable
+    /// date are pulled in to the new end date if they are after it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 56;
+    /// let new_end_date = now + 1000;
+    ///
+    /// fluxity_client::accelerate_lockup(&lockup_id, &new_end_date);
+    /// ```
+    fn accelerate_lockup(e: Env, id: u64, new_end_date: u64) -> Result<(), errors::CustomErrors> {
+        let mut lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        lockup.sender.require_auth();
+
+        if lockup.is_cancelled {
+            return Err(errors::CustomErrors::LockupIsCanceled);
+        }
+
+        let current_date = current_date_for(&e, &lockup);
+
+        if new_end_date < current_date
+            || new_end_date <= lockup.start_date
+            || new_end_date > lockup.end_date
+        {
+            return Err(errors::CustomErrors::InvalidEndDate);
+        }
+
+        lockup.end_date = new_end_date;
+        lockup.cliff_date = lockup.cliff_date.min(new_end_date);
+        lockup.cancellable_date = lockup.cancellable_date.min(new_end_date);
+
+        for index in 0..lockup.tranches.len() {
+            let (date, amount) = lockup.tranches.get_unchecked(index);
+
+            if date > new_end_date {
+                lockup.tranches.set(index, (new_end_date, amount));
+            }
+        }
+
+        storage::set_lockup(&e, id, &lockup);
+
+        events::publish_lockup_accelerated_event(&e, id);
+
+        Ok(())
+    }
+
+    /// Swaps the token of a lockup which hasn't started yet, requires the sender's
+    /// auth. The funded amount is refunded in the old token and pulled in the new one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    /// let new_token = Address::random(&env);
+    ///
+    /// fluxity_client::change_lockup_token(&lockup_id, &new_token);
+    /// ```
+    fn change_lockup_token(
+        e: Env,
+        id: u64,
+        new_token: Address,
+    ) -> Result<(), errors::CustomErrors> {
+        let mut lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        lockup.sender.require_auth();
+
+        if lockup.is_cancelled {
+            return Err(errors::CustomErrors::LockupIsCanceled);
+        }
+
+        if current_date_for(&e, &lockup) >= lockup.start_date || utils::paid_out(&lockup) > 0 {
+            return Err(errors::CustomErrors::LockupAlreadyStarted);
+        }
+
+        let old_token = lockup.token.clone();
+
+        lockup.token = new_token.clone();
+
+        storage::set_lockup(&e, id, &lockup);
+        storage::decrease_locked_balance(&e, &old_token, &lockup.funded_amount);
+        storage::increase_locked_balance(&e, &new_token, &lockup.funded_amount);
+
+        token::transfer(&e, &old_token, &lockup.sender, &lockup.funded_amount);
+        token::transfer_from(&e, &new_token, &lockup.sender, &lockup.funded_amount);
+
+        events::publish_lockup_token_changed_event(&e, id, &new_token);
+
+        Ok(())
+    }
+
+    /// Adds funding to a lockup that was created with an initial deposit lower
+    /// than its amount, withdrawals are capped at the funded amount
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 56;
+    /// let amount = 700000000;
+    ///
+    /// fluxity_client::fund_lockup(&lockup_id, &amount);
+    /// ```
+    fn fund_lockup(e: Env, id: u64, amount: i128) -> Result<i128, errors::CustomErrors> {
+        let mut lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        lockup.sender.require_auth();
+
+        if lockup.is_cancelled {
+            return Err(errors::CustomErrors::LockupIsCanceled);
+        }
+
+        if amount <= 0 || lockup.funded_amount + amount > lockup.amount {
+            return Err(errors::CustomErrors::InvalidAmount);
+        }
+
+        lockup.funded_amount += amount;
+
+        storage::set_lockup(&e, id, &lockup);
+
+        token::transfer_from(&e, &lockup.token, &lockup.sender, &amount);
+        storage::increase_locked_balance(&e, &lockup.token, &amount);
+
+        events::publish_lockup_funded_event(&e, id);
+
+        Ok(lockup.funded_amount)
+    }
+
+    /// Returns the funded amount and the total amount of a lockup, a lockup is
+    /// underfunded when the first is lower than the second
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 56;
+    ///
+    /// let (funded_amount, amount) = fluxity_client::get_lockup_funding(&lockup_id);
+    /// ```
+    fn get_lockup_funding(e: Env, id: u64) -> Result<(i128, i128), errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        Ok((lockup.funded_amount, lockup.amount))
+    }
+
+    /// Deposits tokens into the sender's pool, lockups created with the `from_pool`
+    /// option draw their deposit from it instead of requiring an allowance each time.
+    /// Returns the pool balance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let sender = Address::random(&env);
+    /// let token = Address::random(&env);
+    /// let amount = 700000000;
+    ///
+    /// fluxity_client::deposit(&sender, &token, &amount);
+    /// ```
+    fn deposit(
+        e: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, errors::CustomErrors> {
+        sender.require_auth();
+
+        if amount <= 0 {
+            return Err(errors::CustomErrors::InvalidAmount);
+        }
+
+        token::transfer_from(&e, &token, &sender, &amount);
+
+        storage::increase_sender_pool(&e, &sender, &token, &amount);
+        storage::increase_locked_balance(&e, &token, &amount);
+
+        Ok(storage::get_sender_pool(&e, &sender, &token))
+    }
+
+    /// Withdraws unused tokens from the sender's pool, returns the remaining pool balance
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let sender = Address::random(&env);
+    /// let token = Address::random(&env);
+    /// let amount = 700000000;
+    ///
+    /// fluxity_client::withdraw_pool(&sender, &token, &amount);
+    /// ```
+    fn withdraw_pool(
+        e: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, errors::CustomErrors> {
+        sender.require_auth();
+
+        if amount <= 0 {
+            return Err(errors::CustomErrors::InvalidAmount);
+        }
+
+        storage::decrease_sender_pool(&e, &sender, &token, &amount)?;
+        storage::decrease_locked_balance(&e, &token, &amount);
+
+        token::transfer(&e, &token, &sender, &amount);
+
+        Ok(storage::get_sender_pool(&e, &sender, &token))
+    }
+
+    /// Returns the balance of the sender's pool for a token
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let sender = Address::random(&env);
+    /// let token = Address::random(&env);
+    ///
+    /// fluxity_client::get_sender_pool(&sender, &token);
+    /// ```
+    fn get_sender_pool(e: Env, sender: Address, token: Address) -> i128 {
+        storage::get_sender_pool(&e, &sender, &token)
+    }
+
+    /// Stores the fields shared by many streams (sender, token, rate, cliff and
+    /// cancellability) so they can be created with a smaller payload
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let template = LockupTemplate {
+    ///     sender: Address::random(&env),
+    ///     token: Address::random(&env),
+    ///     rate: Rate::Monthly,
+    ///     cliff_duration: 0,
+    ///     cancellable: true,
+    /// };
+    ///
+    /// fluxity_client::register_template(&template);
+    /// ```
+    fn register_template(
+        e: Env,
+        template: types::LockupTemplate,
+    ) -> Result<u64, errors::CustomErrors> {
+        template.sender.require_auth();
+
+        let id = storage::get_latest_template_id(&e);
+
+        storage::set_template(&e, id, &template);
+        storage::increment_latest_template_id(&e, &id);
+
+        Ok(id)
+    }
+
+    /// Returns a template by id
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let template_id = 3;
+    ///
+    /// fluxity_client::get_template(&template_id);
+    /// ```
+    fn get_template(e: Env, id: u64) -> Result<types::LockupTemplate, errors::CustomErrors> {
+        storage::get_template_by_id(&e, &id)
+    }
+
+    /// Creates an stream using the common fields of a registered template
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let template_id = 3;
+    /// let receiver = Address::random(&env);
+    ///
+    /// fluxity_client::create_stream_from_template(&template_id, &receiver, &20000000, &now, &(now + 1000));
+    /// ```
+    fn create_stream_from_template(
+        e: Env,
+        template_id: u64,
+        receiver: Address,
+        amount: i128,
+        start_date: u64,
+        end_date: u64,
+    ) -> Result<u64, errors::CustomErrors> {
+        let template = storage::get_template_by_id(&e, &template_id)?;
+
+        let cancellable_date = if template.cancellable {
+            start_date
+        } else {
+            end_date
+        };
+
+        let cliff_date = start_date
+            .checked_add(template.cliff_duration)
+            .ok_or(errors::CustomErrors::InvalidDuration)?;
+
+        let params = types::LockupInput {
+            sender: template.sender,
+            receiver,
+            token: template.token,
+            amount,
+            cancellable_date,
+            cliff_date,
+            start_date,
+            end_date,
+            rate: template.rate,
+            options: Default::default(),
+        };
+
+        create_lockup(&e, params, false, vec![&e])
+    }
+
+    /// Extends the TTL of many lockups at once so long lockups don't expire, missing
+    /// lockups are skipped. Returns the number of extended lockups
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ids = vec![&env, 20, 21, 22];
+    ///
+    /// fluxity_client::bump_lockups_ttl(&ids);
+    /// ```
+    fn bump_lockups_ttl(e: Env, ids: Vec<u64>) -> Result<u32, errors::CustomErrors> {
+        if ids.len() > MAX_BATCH_SIZE {
+            return Err(errors::CustomErrors::BatchTooLarge);
+        }
+
+        let mut bumped = 0;
+
+        for id in ids.iter() {
+            if storage::extend_lockup_ttl(&e, id) {
+                bumped += 1;
+            }
+        }
+
+        extend_ttl::extend_contract_ttl(&e);
+
+        Ok(bumped)
+    }
+
+    /// Returns the storage footprint of a lockup as `(entry_size_bytes, bump_target)`,
+    /// the size is the length of the XDR encoded lockup. Contracts can't read the TTL
+    /// of an entry, so the second value is the number of ledgers the contract extends
+    /// the entry to whenever it is written or bumped, not the TTL it has left
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lockup_id = 20;
+    ///
+    /// let (size, bump_target) = fluxity_client::get_lockup_storage_info(&lockup_id);
+    /// ```
+    fn get_lockup_storage_info(e: Env, id: u64) -> Result<(u32, u32), errors::CustomErrors> {
+        let lockup = storage::get_lockup_by_id(&e, &id)?;
+
+        Ok((lockup.to_xdr(&e).len(), extend_ttl::BUMP_AMOUNT))
+    }
+
+    /// Transfers the tokens that were sent to the contract directly (not through
+    /// a lockup) to the specified address, only callable by the admin. Nothing is
+    /// swept until the legacy lockups are migrated with `migrate_lockups`, since what
+    /// they hold isn't counted in the locked balance before
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let token = Address::random(&env);
+    /// let to = Address::random(&env);
+    ///
+    /// fluxity_client::sweep_token(&token, &to);
+    /// ```
+    fn sweep_token(e: Env, token: Address, to: Address) -> Result<i128, errors::CustomErrors> {
+        let admin = storage::get_admin(&e)?;
+
+        admin.require_auth();
+
+        if !storage::is_legacy_migration_complete(&e) {
+            return Ok(0);
+        }
+
+        let balance = token::balance(&e, &token, &e.current_contract_address());
+        let locked = storage::get_locked_balance(&e, &token);
+        let surplus = balance - locked;
+
+        if surplus <= 0 {
+            return Ok(0);
+        }
+
+        token::transfer(&e, &token, &to, &surplus);
+
+        Ok(surplus)
+    }
+
+    /// Returns the amount of a token the contract holds for lockups and sender
+    /// pools, anything above it can be swept
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let token = Address::random(&env);
+    ///
+    /// fluxity_client::get_locked_balance(&token);
+    /// ```
+    fn get_locked_balance(e: Env, token: Address) -> i128 {
+        storage::get_locked_balance(&e, &token)
+    }
+}
+
+fn create_lockup(
+    e: &Env,
+    params: types::LockupInput,
+    is_vesting: bool,
+    tranches: Vec<(u64, i128)>,
+) -> Result<u64, errors::CustomErrors> {
+    if params.amount <= 0 {
+        return Err(errors::CustomErrors::InvalidAmount);
+    }
+
+    if params.sender == e.current_contract_address() {
+        return Err(errors::CustomErrors::InvalidSender);
+    }
+
+    if params.sender == params.receiver {
+        return Err(errors::CustomErrors::InvalidReceiver);
+    }
+
+    if params.start_date >= params.end_date {
+        return Err(errors::CustomErrors::InvalidStartDate);
+    }
+
+    // The configured durations are in seconds
+    let duration = span_in_seconds(
+        params.options.use_ledger_seq,
+        params.end_date - params.start_date,
+    );
+
+    if duration < storage::get_min_duration(e) {
+        return Err(errors::CustomErrors::InvalidDuration);
+    }
+
+    if let Some(max_duration) = storage::get_max_duration(e) {
+        if duration > max_duration {
+            return Err(errors::CustomErrors::DurationTooLong);
+        }
+    }
+
+    if params.cancellable_date > params.end_date {
+        return Err(errors::CustomErrors::InvalidCancellableDate);
+    }
+
+    if params.cliff_date < params.start_date || params.cliff_date > params.end_date {
+        return Err(errors::CustomErrors::InvalidCliffDate);
+    }
+
+    if params.options.strict_schedule && params.cliff_date == params.end_date {
+        return Err(errors::CustomErrors::InvalidCliffDate);
+    }
+
+    if is_vesting && params.options.use_ledger_seq {
+        return Err(errors::CustomErrors::UnsupportedForVesting);
+    }
+
+    if params.options.mode == types::StreamMode::Reverse {
+        if is_vesting {
+            return Err(errors::CustomErrors::UnsupportedForVesting);
+        }
+
+        if params.cliff_date != params.start_date {
+            return Err(errors::CustomErrors::InvalidCliffDate);
+        }
+    }
+
+    if let types::ExternalRef::Some(external_ref) = &params.options.external_ref {
+        if storage::get_lockup_id_by_external_ref(e, external_ref).is_some() {
+            return Err(errors::CustomErrors::DuplicateExternalRef);
+        }
+    }
+
+    let deposit = params.options.initial_deposit.unwrap_or(params.amount);
+
+    if deposit < 0 || deposit > params.amount {
+        return Err(errors::CustomErrors::InvalidDeposit);
+    }
+
+    // A deposit pulled through an allowance doesn't prove the sender agreed to the
+    // lockup, and a zero deposit pulls nothing at all
+    params.sender.require_auth();
+
+    let skimmed_fee = charge_creation_fee(e, &params, deposit)?;
+
+    // Periods of a vesting unlocking less than a unit would unlock nothing
+    if is_vesting
+        && tranches.is_empty()
+        && utils::calculate_period_amount(
+            params.amount,
+            params.start_date,
+            params.end_date,
+            params.rate,
+        ) < 1
+    {
+        return Err(errors::CustomErrors::RateTooGranular);
+    }
+
+    if !tranches.is_empty() {
+        utils::validate_tranches(
+            &tranches,
+            params.start_date,
+            params.end_date,
+            params.amount - skimmed_fee,
+        )?;
+    }
+
+    if deposit > 0 && params.options.from_pool {
+        // Pooled tokens are already counted in the locked balance
+        storage::decrease_sender_pool(e, &params.sender, &params.token, &deposit)?;
+        storage::decrease_locked_balance(e, &params.token, &skimmed_fee);
+    } else if deposit > 0 {
+        token::transfer_from(e, &params.token, &params.sender, &deposit);
+        storage::increase_locked_balance(e, &params.token, &(deposit - skimmed_fee));
+    }
+
+    if skimmed_fee > 0 {
+        let fee_recipient = storage::get_fee_config(e).unwrap().fee_recipient;
+
+        token::transfer(e, &params.token, &fee_recipient, &skimmed_fee);
+        storage::increase_fees_collected(e, &params.token, &skimmed_fee);
+    }
+
+    let id = storage::get_latest_lockup_id(e) + 1;
+    let tokenized = params.options.tokenized;
+    let mut lockup: types::Lockup = params.into();
+
+    lockup.amount -= skimmed_fee;
+    lockup.funded_amount -= skimmed_fee;
+    lockup.is_vesting = is_vesting;
+    lockup.tranches = tranches;
+    lockup.created_at = e.ledger().timestamp();
+
+    if tokenized {
+        let nft = storage::get_position_nft(e).ok_or(errors::CustomErrors::PositionNftNotSet)?;
+
+        position_nft::mint(e, &nft, &lockup.receiver, id);
+        lockup.position = types::Position::Tokenized(nft, id);
+    }
+
+    storage::set_lockup(e, id, &lockup);
+    storage::increase_active_count(e);
+    storage::add_receiver_lockup_id(e, &lockup.receiver, id);
+    storage::add_sender_lockup_id(e, &lockup.sender, id);
+
+    if let types::ExternalRef::Some(external_ref) = &lockup.external_ref {
+        storage::set_external_ref(e, external_ref, id);
+    }
+
+    storage::set_latest_lockup_id(e, &id);
+    events::publish_lockup_created_event(e, id, &lockup.receiver, lockup.created_at);
+
+    Ok(id)
+}
+
+/// Charges the creation fee, a fee in a separate token is pulled from the sender
+/// directly while a fee in the lockup token is skimmed from the deposit. Returns
+/// the skimmed fee the lockup amount has to be reduced by
+fn charge_creation_fee(
+    e: &Env,
+    params: &types::LockupInput,
+    deposit: i128,
+) -> Result<i128, errors::CustomErrors> {
+    let Some(config) = storage::get_fee_config(e) else {
+        return Ok(0);
+    };
+
+    let fee = match &config.fee_strategy {
+        types::FeeStrategy::None => utils::calculate_fee(params.amount, config.fee_bps)?,
+        types::FeeStrategy::Some(strategy) => fee_strategy::compute_fee(
+            e,
+            strategy,
+            &params.token,
+            params.amount,
+            span_in_seconds(
+                params.options.use_ledger_seq,
+                params.end_date - params.start_date,
+            ),
+        ),
+    };
+
+    if fee < 0 || fee > params.amount {
+        return Err(errors::CustomErrors::InvalidFeeConfig);
+    }
+
+    if fee == 0 {
+        return Ok(0);
+    }
+
+    match config.fee_token {
+        types::FeeToken::None => {
+            if deposit < fee {
+                return Err(errors::CustomErrors::InvalidDeposit);
+            }
+
+            Ok(fee)
+        }
+        types::FeeToken::Some(fee_token) => {
+            if token::allowance(e, &fee_token, &params.sender) < fee {
+                return Err(errors::CustomErrors::InsufficientFeeAllowance);
+            }
+
+            token::transfer_from_to(e, &fee_token, &params.sender, &config.fee_recipient, &fee);
+            storage::increase_fees_collected(e, &fee_token, &fee);
+
+            Ok(0)
+        }
+    }
+}
+
+/// Returns the withdrawn amount of a lockup after withdrawing `amount` more. It can't
+/// exceed the lockup amount, so an overflow means the accounting is corrupted
+fn add_withdrawn(lockup: &types::Lockup, amount: i128) -> Result<i128, errors::CustomErrors> {
+    lockup
+        .withdrawn
+        .checked_add(amount)
+        .ok_or(errors::CustomErrors::AmountOverflows)
+}
+
+/// Returns false while the condition of a conditional lockup isn't met
+fn is_released(e: &Env, id: u64, lockup: &types::Lockup) -> bool {
+    match &lockup.condition {
+        types::Condition::None => true,
+        types::Condition::Some(condition) => release_condition::is_released(e, condition, id),
+    }
+}
+
+/// Validates a withdrawal and returns the amount to transfer to the receiver,
+/// an `amount` of 0 means everything withdrawable
+fn withdrawal_amount(
+    e: &Env,
+    id: u64,
+    lockup: &types::Lockup,
+    amount: i128,
+) -> Result<i128, errors::CustomErrors> {
+    let (amount_to_transfer, is_final) = validate_withdrawal(e, id, lockup, amount)?;
+
+    ensure_min_withdraw(e, amount_to_transfer, is_final)?;
+
+    Ok(amount_to_transfer)
+}
+
+/// Validates a withdrawal except for the minimum, returns the amount to transfer and
+/// whether it's the last withdrawal of the lockup
+fn validate_withdrawal(
+    e: &Env,
+    id: u64,
+    lockup: &types::Lockup,
+    amount: i128,
+) -> Result<(i128, bool), errors::CustomErrors> {
+    if amount < 0 {
+        return Err(errors::CustomErrors::AmountUnderflows);
+    }
+
+    let current_date = current_date_for(e, lockup);
+
+    // Only withdrawals are gated by the unlock date, the lockup keeps accruing
+    if current_date < lockup.withdraw_unlock_date {
+        return Err(errors::CustomErrors::WithdrawLocked);
+    }
+
+    if !is_released(e, id, lockup) {
+        return Err(errors::CustomErrors::ConditionNotMet);
+    }
+
+    // The receiver can still claim what was vested at cancellation, if it was
+    // not pushed to them by `cancel_lockup`
+    if lockup.is_cancelled {
+        if utils::calculate_withdrawable(lockup, current_date)? == 0 {
+            return Err(errors::CustomErrors::LockupIsCanceled);
+        }
+    } else if current_date <= lockup.start_date {
+        return Err(errors::CustomErrors::LockupNotStartedYet);
+    }
+
+    let date = utils::effective_date(lockup, current_date);
+
+    if date <= lockup.cliff_date {
+        return Ok((0, false));
+    }
+
+    let amounts = utils::calculate_lockup_amounts(lockup, date)?;
+
+    let withdrawable = amounts
+        .receiver_amount
+        .checked_sub(utils::paid_out(lockup))
+        .ok_or(errors::CustomErrors::AmountOverflows)?;
+    let funded = lockup
+        .funded_amount
+        .checked_sub(utils::paid_out(lockup))
+        .ok_or(errors::CustomErrors::AmountOverflows)?;
+
+    if withdrawable < amount {
+        return Err(errors::CustomErrors::SpecifiedAmountIsGreaterThanWithdrawable);
+    }
+
+    let mut amount_to_transfer = amount;
+
+    if amount == 0 {
+        amount_to_transfer = withdrawable.min(funded);
+    }
+
+    if funded < amount || (amount_to_transfer == 0 && withdrawable > 0) {
+        return Err(errors::CustomErrors::InsufficientFunding);
+    }
+
+    // Nothing accrues after the end or the cancellation, so withdrawing everything
+    // left then is the last withdrawal and may be below the minimum
+    let is_final = (lockup.is_cancelled || current_date >= lockup.end_date)
+        && amount_to_transfer == withdrawable.min(funded);
+
+    Ok((amount_to_transfer, is_final))
+}
+
+/// Rejects withdrawals below the minimum, except the last one of a lockup
+fn ensure_min_withdraw(e: &Env, amount: i128, is_final: bool) -> Result<(), errors::CustomErrors> {
+    if amount > 0 && amount < storage::get_min_withdraw(e) && !is_final {
+        return Err(errors::CustomErrors::WithdrawBelowMinimum);
+    }
+
+    Ok(())
+}
+
+/// Counts the lockup out of the active ones if it's settled, callers only get here
+/// right after changing a lockup which wasn't settled, so it's counted out once
+fn track_settlement(e: &Env, lockup: &types::Lockup) {
+    if utils::is_settled(lockup, current_date_for(e, lockup)) {
+        storage::decrease_active_count(e);
+    }
+}
+
+/// Rejects paying the receiver of a group stream, the members withdraw their shares
+/// through `withdraw_group_share` instead
+fn ensure_not_group(e: &Env, id: u64) -> Result<(), errors::CustomErrors> {
+    if storage::get_group_shares(e, id).is_some() {
+        return Err(errors::CustomErrors::UnsupportedForGroup);
+    }
+
+    Ok(())
+}
+
+/// Returns where the receiver's payouts go, the default destination of the lockup if
+/// the receiver set one
+fn payout_address(e: &Env, id: u64, lockup: &types::Lockup) -> Address {
+    storage::get_default_destination(e, id).unwrap_or(lockup.receiver.clone())
+}
+
+/// Returns the current date in the clock the lockup is scheduled with, which is the
+/// ledger sequence for lockups created with `use_ledger_seq` and the timestamp otherwise
+fn current_date_for(e: &Env, lockup: &types::Lockup) -> u64 {
+    if lockup.use_ledger_seq {
+        e.ledger().sequence().into()
+    } else {
+        e.ledger().timestamp()
+    }
+}
+
+/// Converts a span in the clock of a lockup to seconds, spans of lockups following
+/// the ledger sequence are estimated from the ledger close time the network targets
+fn span_in_seconds(use_ledger_seq: bool, span: u64) -> u64 {
+    if use_ledger_seq {
+        span.saturating_mul(LEDGER_CLOSE_SECONDS)
+    } else {
+        span
+    }
+}
+
+/// Publishes the withdrawn event, and the metrics event when the withdrawal buckets
+/// are set, unless the lockup opted out of withdrawn events
+fn publish_withdrawn_events(e: &Env, id: u64, lockup: &types::Lockup, amount: i128) {
+    if !lockup.emit_events {
+        return;
+    }
+
+    events::publish_lockup_withdrawn_event(e, id, &lockup.sender);
+
+    if let Some(buckets) = storage::get_withdrawal_buckets(e) {
+        events::publish_withdrawal_metrics_event(e, id, amount, &buckets);
+    }
+}
+
+fn lockup_detail(
+    e: &Env,
+    lockup: types::Lockup,
+) -> Result<types::LockupDetail, errors::CustomErrors> {
+    let current_date = current_date_for(e, &lockup);
+
+    Ok(types::LockupDetail {
+        status: utils::get_lockup_status(&lockup, current_date),
+        vested: utils::calculate_vested(&lockup, current_date)?,
+        withdrawable: utils::calculate_withdrawable(&lockup, current_date)?,
+        progress_bps: utils::calculate_progress_bps(&lockup, current_date),
+        lockup,
+    })
+}
+
+fn cancel(
+    e: &Env,
+    id: u64,
+    mut lockup: types::Lockup,
+    push_to_receiver: bool,
+) -> Result<(i128, i128), errors::CustomErrors> {
+    if push_to_receiver {
+        ensure_not_group(e, id)?;
+    }
+
+    let current_date = current_date_for(e, &lockup);
+
+    utils::check_cancellable(&lockup, current_date)?;
+
+    let (sender_amount, receiver_amount) = cancellation_amounts(&lockup, current_date)?;
+    let receiver_amount = if push_to_receiver { receiver_amount } else { 0 };
+    let cancel_fee = calculate_cancel_fee(e, sender_amount)?;
+
+    lockup.is_cancelled = true;
+    lockup.cancelled_date = current_date;
+    lockup.withdrawn = add_withdrawn(&lockup, receiver_amount)?;
+
+    // Taken after every fallible step, batch cancellations keep going after a failed
+    // item so an early return would leave the lock set
+    storage::acquire_reentrancy_lock(e)?;
+
+    storage::set_lockup(e, id, &lockup);
+    track_settlement(e, &lockup);
+    storage::decrease_locked_balance(e, &lockup.token, &(sender_amount + receiver_amount));
+
+    if receiver_amount > 0 {
+        token::transfer(
+            e,
+            &lockup.token,
+            &payout_address(e, id, &lockup),
+            &receiver_amount,
+        );
+    }
+
+    if sender_amount > cancel_fee {
+        token::transfer(
+            e,
+            &lockup.token,
+            &lockup.sender,
+            &(sender_amount - cancel_fee),
+        );
+    }
+
+    if cancel_fee > 0 {
+        let fee_config = storage::get_fee_config(e).unwrap();
+
+        token::transfer(e, &lockup.token, &fee_config.fee_recipient, &cancel_fee);
+        storage::increase_fees_collected(e, &lockup.token, &cancel_fee);
+    }
+
+    storage::release_reentrancy_lock(e);
+
+    events::publish_lockup_cancelled_event(e, id, &lockup.sender);
+
+    Ok((sender_amount - cancel_fee, receiver_amount))
+}
+
+fn calculate_cancel_fee(e: &Env, sender_amount: i128) -> Result<i128, errors::CustomErrors> {
+    match storage::get_fee_config(e) {
+        Some(fee_config) => utils::calculate_fee(sender_amount, fee_config.cancel_fee_bps),
+        None => Ok(0),
+    }
+}
+
+/// Returns the amounts the sender and the receiver would get if the lockup was
+/// cancelled at the given date
+fn cancellation_amounts(
+    lockup: &types::Lockup,
+    current_date: u64,
+) -> Result<(i128, i128), errors::CustomErrors> {
+    let amounts = utils::calculate_lockup_amounts(lockup, current_date)?;
+
+    let paid_out = utils::paid_out(lockup);
+
+    if amounts.receiver_amount < paid_out {
+        return Err(errors::CustomErrors::WithdrawnExceedsVested);
+    }
+
+    // The rounding remainder (if any) is attributed to the sender, so both sides
+    // always add up to exactly what is left in the lockup. If the lockup is not
+    // fully funded, the receiver is paid first and the sender gets what is left
+    let available = lockup.funded_amount - paid_out;
+    let remainder = lockup.amount - amounts.sender_amount - amounts.receiver_amount;
+    let receiver_amount = (amounts.receiver_amount - paid_out).min(available);
+    let sender_amount = (amounts.sender_amount + remainder).min(available - receiver_amount);
+
+    if sender_amount < 0 || sender_amount + receiver_amount > available {
+        return Err(errors::CustomErrors::LockupBalanceExceeded);
+    }
+
+    Ok((sender_amount, receiver_amount))
 }
@@ -1,8 +1,47 @@
-use soroban_sdk::contracttype;
+use soroban_sdk::{contracttype, Address, BytesN};
 
 #[contracttype]
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub enum DataKey {
     Lockup(u64),
     LatestLockupId,
+    EventSequence,
+    Admin,
+    LockedBalance(Address),
+    ReceiverLockups(Address),
+    SenderLockups(Address),
+    ExternalRef(BytesN<32>),
+    Template(u64),
+    LatestTemplateId,
+    SenderPool(Address, Address),
+    WithdrawNonces(u64),
+    CancelNonce(u64),
+    FeeConfig,
+    PositionNft,
+    DormancyPeriod,
+    ReentrancyLock,
+    MinDuration,
+    DefaultRate,
+    WithdrawalBuckets,
+    FeesCollected(Address),
+    MinWithdraw,
+    ActiveCount,
+    MaxDuration,
+    DefaultDestination(u64),
+    LockupIndexLen(LockupIndex),
+    LockupIndexChunk(LockupIndex, u32),
+    ReceiverLockupPosition(u64),
+    LastLockupId,
+    GroupShares(u64),
+    GroupWithdrawn(u64, Address),
+    LegacyMigrationCursor,
+}
+
+/// The lockup ids of a party, stored in chunks so the index never outgrows the
+/// size of a single entry
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum LockupIndex {
+    Receiver(Address),
+    Sender(Address),
 }
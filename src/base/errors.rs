@@ -10,6 +10,10 @@ pub enum CustomErrors {
     InvalidCliffDate = 13,
     InvalidCancellableDate = 14,
     InvalidVestingDates = 15,
+    InvalidDuration = 16,
+    InvalidDeposit = 17,
+    DuplicateExternalRef = 18,
+    InvalidTranches = 19,
     LockupNotStartedYet = 20,
     LockupAlreadyCanceled = 21,
     LockupAlreadySettled = 22,
@@ -18,4 +22,39 @@ pub enum CustomErrors {
     LockupIsCanceled = 25,
     SpecifiedAmountIsGreaterThanWithdrawable = 26,
     AmountUnderflows = 27,
+    TemplateNotFound = 28,
+    LockupBalanceExceeded = 29,
+    AlreadyInitialized = 30,
+    NotInitialized = 31,
+    InsufficientFunding = 32,
+    WithdrawnExceedsVested = 33,
+    LockupSenderMismatch = 34,
+    BatchTooLarge = 35,
+    InsufficientPoolBalance = 36,
+    WithdrawLocked = 37,
+    /// Returned by methods which only support linear streams when called on a vesting
+    UnsupportedForVesting = 38,
+    InvalidEndDate = 39,
+    InsufficientFeeAllowance = 40,
+    InvalidFeeConfig = 41,
+    LockupNotEndedYet = 42,
+    RateTooGranular = 43,
+    PositionNftNotSet = 44,
+    PositionOwnerMismatch = 45,
+    LockupNotDormant = 46,
+    LockupNotCancelled = 47,
+    AmountOverflows = 48,
+    ConditionNotMet = 49,
+    // 50 was `InvalidRate`, it's kept unused so clients don't misread the code
+    Reentrancy = 51,
+    LockupAlreadyStarted = 52,
+    InvalidSender = 53,
+    TransferNotAllowed = 54,
+    WithdrawBelowMinimum = 55,
+    DurationTooLong = 56,
+    LockupUndecodable = 57,
+    UnsupportedForGroup = 58,
+    NotGroupMember = 59,
+    /// Returned by methods counting periods of time when the dates are ledger sequences
+    UnsupportedForLedgerSeq = 60,
 }
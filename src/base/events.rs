@@ -1,21 +1,182 @@
-use soroban_sdk::{symbol_short, Env};
+use soroban_sdk::{symbol_short, Address, Env};
 
-pub fn publish_lockup_created_event(e: &Env, id: u64) {
-    e.events()
-        .publish((symbol_short!("LOCKUP"), symbol_short!("CREATED")), id);
+use super::storage;
+use super::types::{FeeConfig, Rate, WithdrawalBuckets};
+
+/// The receiver is a topic so wallets can subscribe to the lockups sent to them
+/// Includes the creation date so indexers don't have to look up the ledger
+pub fn publish_lockup_created_event(e: &Env, id: u64, receiver: &Address, created_at: u64) {
+    e.events().publish(
+        (
+            symbol_short!("LOCKUP"),
+            symbol_short!("CREATED"),
+            receiver.clone(),
+        ),
+        (id, storage::next_event_sequence(e), created_at),
+    );
+}
+
+/// The sender is a topic so senders can subscribe to the activity on their lockups
+pub fn publish_lockup_cancelled_event(e: &Env, id: u64, sender: &Address) {
+    e.events().publish(
+        (
+            symbol_short!("LOCKUP"),
+            symbol_short!("CANCELLED"),
+            sender.clone(),
+        ),
+        (id, storage::next_event_sequence(e)),
+    );
+}
+
+/// The sender is a topic so senders can subscribe to the activity on their lockups
+pub fn publish_lockup_withdrawn_event(e: &Env, id: u64, sender: &Address) {
+    e.events().publish(
+        (
+            symbol_short!("LOCKUP"),
+            symbol_short!("WITHDRAWN"),
+            sender.clone(),
+        ),
+        (id, storage::next_event_sequence(e)),
+    );
+}
+
+/// Classifies the withdrawn amount in the topic so indexers can filter the large
+/// withdrawals without decoding the data
+pub fn publish_withdrawal_metrics_event(
+    e: &Env,
+    id: u64,
+    amount: i128,
+    buckets: &WithdrawalBuckets,
+) {
+    let bucket = if amount >= buckets.large_from {
+        symbol_short!("LARGE")
+    } else if amount >= buckets.medium_from {
+        symbol_short!("MEDIUM")
+    } else {
+        symbol_short!("SMALL")
+    };
+
+    e.events().publish(
+        (symbol_short!("METRICS"), symbol_short!("WITHDRAWN"), bucket),
+        (id, storage::next_event_sequence(e), amount),
+    );
+}
+
+/// Includes the added amount and the new total amount so consumers can update
+/// their accrual projections without reading the lockup
+pub fn publish_lockup_topup_event(e: &Env, id: u64, added_amount: i128, amount: i128) {
+    e.events().publish(
+        (symbol_short!("LOCKUP"), symbol_short!("TOPUP")),
+        (id, storage::next_event_sequence(e), added_amount, amount),
+    );
+}
+
+pub fn publish_lockup_transferred_event(e: &Env, id: u64) {
+    e.events().publish(
+        (symbol_short!("LOCKUP"), symbol_short!("TRANSFER")),
+        (id, storage::next_event_sequence(e)),
+    );
+}
+
+pub fn publish_lockup_accelerated_event(e: &Env, id: u64) {
+    e.events().publish(
+        (symbol_short!("LOCKUP"), symbol_short!("ACCEL")),
+        (id, storage::next_event_sequence(e)),
+    );
+}
+
+/// The sender is a topic so senders can subscribe to the activity on their lockups
+pub fn publish_lockup_reclaimed_event(e: &Env, id: u64, sender: &Address, amount: i128) {
+    e.events().publish(
+        (
+            symbol_short!("LOCKUP"),
+            symbol_short!("RECLAIM"),
+            sender.clone(),
+        ),
+        (id, storage::next_event_sequence(e), amount),
+    );
+}
+
+pub fn publish_lockup_token_changed_event(e: &Env, id: u64, token: &Address) {
+    e.events().publish(
+        (symbol_short!("LOCKUP"), symbol_short!("TOKEN")),
+        (id, storage::next_event_sequence(e), token.clone()),
+    );
+}
+
+pub fn publish_lockup_funded_event(e: &Env, id: u64) {
+    e.events().publish(
+        (symbol_short!("LOCKUP"), symbol_short!("FUNDED")),
+        (id, storage::next_event_sequence(e)),
+    );
+}
+
+pub fn publish_lockup_settled_event(e: &Env, id: u64) {
+    e.events().publish(
+        (symbol_short!("LOCKUP"), symbol_short!("SETTLED")),
+        (id, storage::next_event_sequence(e)),
+    );
+}
+
+pub fn publish_lockup_destination_event(e: &Env, id: u64) {
+    e.events().publish(
+        (symbol_short!("LOCKUP"), symbol_short!("DEST")),
+        (id, storage::next_event_sequence(e)),
+    );
+}
+
+pub fn publish_admin_changed_event(e: &Env, admin: &Address) {
+    e.events().publish(
+        (symbol_short!("CONFIG"), symbol_short!("ADMIN")),
+        (admin.clone(), storage::next_event_sequence(e)),
+    );
+}
+
+pub fn publish_fee_config_changed_event(e: &Env, config: &FeeConfig) {
+    e.events().publish(
+        (symbol_short!("CONFIG"), symbol_short!("FEE")),
+        (config.clone(), storage::next_event_sequence(e)),
+    );
+}
+
+pub fn publish_position_nft_changed_event(e: &Env, nft: &Address) {
+    e.events().publish(
+        (symbol_short!("CONFIG"), symbol_short!("NFT")),
+        (nft.clone(), storage::next_event_sequence(e)),
+    );
+}
+
+pub fn publish_default_rate_changed_event(e: &Env, rate: Rate) {
+    e.events().publish(
+        (symbol_short!("CONFIG"), symbol_short!("RATE")),
+        (rate, storage::next_event_sequence(e)),
+    );
+}
+
+pub fn publish_withdrawal_buckets_changed_event(e: &Env, buckets: &WithdrawalBuckets) {
+    e.events().publish(
+        (symbol_short!("CONFIG"), symbol_short!("BUCKETS")),
+        (buckets.clone(), storage::next_event_sequence(e)),
+    );
 }
 
-pub fn publish_lockup_cancelled_event(e: &Env, id: u64) {
-    e.events()
-        .publish((symbol_short!("LOCKUP"), symbol_short!("CANCELLED")), id);
+pub fn publish_min_withdraw_changed_event(e: &Env, min_withdraw: i128) {
+    e.events().publish(
+        (symbol_short!("CONFIG"), symbol_short!("MINWDRAW")),
+        (min_withdraw, storage::next_event_sequence(e)),
+    );
 }
 
-pub fn publish_lockup_withdrawn_event(e: &Env, id: u64) {
-    e.events()
-        .publish((symbol_short!("LOCKUP"), symbol_short!("WITHDRAWN")), id);
+pub fn publish_max_duration_changed_event(e: &Env, max_duration: u64) {
+    e.events().publish(
+        (symbol_short!("CONFIG"), symbol_short!("MAXDUR")),
+        (max_duration, storage::next_event_sequence(e)),
+    );
 }
 
-pub fn publish_lockup_topup_event(e: &Env, id: u64) {
-    e.events()
-        .publish((symbol_short!("LOCKUP"), symbol_short!("TOPUP")), id);
+pub fn publish_dormancy_period_changed_event(e: &Env, period: u64) {
+    e.events().publish(
+        (symbol_short!("CONFIG"), symbol_short!("DORMANCY")),
+        (period, storage::next_event_sequence(e)),
+    );
 }
@@ -0,0 +1,19 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The interface of an external contract computing the creation fee of a lockup,
+/// used instead of the fixed `fee_bps` when set in the fee config
+#[allow(dead_code)]
+#[contractclient(name = "FeeStrategyClient")]
+pub trait FeeStrategy {
+    fn compute_fee(e: Env, token: Address, amount: i128, duration: u64) -> i128;
+}
+
+pub fn compute_fee(
+    e: &Env,
+    strategy: &Address,
+    token: &Address,
+    amount: i128,
+    duration: u64,
+) -> i128 {
+    FeeStrategyClient::new(e, strategy).compute_fee(token, &amount, &duration)
+}
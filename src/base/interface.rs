@@ -1,13 +1,200 @@
-use soroban_sdk::Env;
+use soroban_sdk::{Address, Bytes, BytesN, Env, Vec};
 
 use super::{errors, types};
 
 pub trait IFluxity {
+    fn initialize(
+        e: Env,
+        admin: Address,
+        config: types::ContractConfig,
+    ) -> Result<(), errors::CustomErrors>;
+    fn get_min_duration(e: Env) -> u64;
+    fn set_max_duration(e: Env, max_duration: u64) -> Result<(), errors::CustomErrors>;
+    fn get_max_duration(e: Env) -> Option<u64>;
+    fn set_fee_config(e: Env, config: types::FeeConfig) -> Result<(), errors::CustomErrors>;
+    fn get_fee_config(e: Env) -> Option<types::FeeConfig>;
+    fn get_fees_collected(e: Env, token: Address) -> i128;
+    fn get_features(e: Env) -> u32;
+    fn set_position_nft(e: Env, nft: Address) -> Result<(), errors::CustomErrors>;
+    fn set_dormancy_period(e: Env, period: u64) -> Result<(), errors::CustomErrors>;
+    fn get_dormancy_period(e: Env) -> u64;
+    fn set_default_rate(e: Env, rate: types::Rate) -> Result<(), errors::CustomErrors>;
+    fn get_default_rate(e: Env) -> Option<types::Rate>;
+    fn set_withdrawal_buckets(
+        e: Env,
+        buckets: types::WithdrawalBuckets,
+    ) -> Result<(), errors::CustomErrors>;
+    fn get_withdrawal_buckets(e: Env) -> Option<types::WithdrawalBuckets>;
+    fn set_min_withdraw(e: Env, min_withdraw: i128) -> Result<(), errors::CustomErrors>;
+    fn get_min_withdraw(e: Env) -> i128;
     fn get_latest_lockup_id(e: Env) -> u64;
+    fn get_active_count(e: Env) -> u64;
     fn get_lockup(e: Env, id: u64) -> Result<types::Lockup, errors::CustomErrors>;
+    fn migrate_lockup(e: Env, id: u64) -> Result<(), errors::CustomErrors>;
+    fn migrate_lockups(e: Env, limit: u32) -> u64;
+    fn get_lockup_input(e: Env, id: u64) -> Result<types::LockupInput, errors::CustomErrors>;
+    fn export_lockup(e: Env, id: u64) -> Result<Bytes, errors::CustomErrors>;
+    fn get_lockup_flat(e: Env, id: u64) -> Result<types::FlatLockup, errors::CustomErrors>;
+    fn get_lockup_amount_scaled(e: Env, id: u64) -> Result<(i128, u32), errors::CustomErrors>;
+    fn get_lockups(
+        e: Env,
+        ids: Vec<u64>,
+    ) -> Result<Vec<Option<types::Lockup>>, errors::CustomErrors>;
+    fn get_lockup_by_external_ref(
+        e: Env,
+        external_ref: BytesN<32>,
+    ) -> Result<u64, errors::CustomErrors>;
+    fn get_lockup_kind(e: Env, id: u64) -> Result<types::LockupKind, errors::CustomErrors>;
+    fn has_cliff(e: Env, id: u64) -> Result<bool, errors::CustomErrors>;
+    fn get_lockup_status(e: Env, id: u64) -> Result<types::LockupStatus, errors::CustomErrors>;
+    fn get_time_since_cancel(e: Env, id: u64) -> Result<u64, errors::CustomErrors>;
+    fn is_lockup_settled(e: Env, id: u64) -> Result<bool, errors::CustomErrors>;
+    fn finalize_if_settled(e: Env, id: u64) -> Result<bool, errors::CustomErrors>;
+    fn finalize_lockup(e: Env, id: u64) -> Result<i128, errors::CustomErrors>;
+    fn reclaim_dormant(e: Env, id: u64) -> Result<i128, errors::CustomErrors>;
+    fn get_withdrawable(e: Env, id: u64) -> Result<i128, errors::CustomErrors>;
+    fn get_withdrawable_at(e: Env, id: u64, timestamp: u64) -> Result<i128, errors::CustomErrors>;
+    fn get_lockup_withdrawn(e: Env, id: u64) -> Result<i128, errors::CustomErrors>;
+    fn get_vested_at(e: Env, id: u64, timestamp: u64) -> Result<i128, errors::CustomErrors>;
+    fn get_next_unlock(e: Env, id: u64) -> Result<(u64, i128), errors::CustomErrors>;
+    fn get_lockup_rate(e: Env, id: u64) -> Result<i128, errors::CustomErrors>;
+    fn get_lockup_detail(e: Env, id: u64) -> Result<types::LockupDetail, errors::CustomErrors>;
+    fn list_lockups_detail(e: Env, cursor: u64, limit: u32) -> (Vec<types::LockupDetail>, u64);
+    fn list_lockups_by_status(
+        e: Env,
+        status: types::LockupStatus,
+        cursor: u64,
+        limit: u32,
+    ) -> (Vec<u64>, u64);
+    fn get_lockups_over_threshold(
+        e: Env,
+        receiver: Address,
+        min_withdrawable: i128,
+        cursor: u32,
+        limit: u32,
+    ) -> (Vec<u64>, u32);
+    fn get_cancellable_lockups(e: Env, sender: Address, cursor: u32, limit: u32)
+        -> (Vec<u64>, u32);
+    fn get_lockups_between_parties(
+        e: Env,
+        sender: Address,
+        receiver: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> (Vec<u64>, u32);
+    fn get_lockups_created_between(e: Env, from: u64, to: u64, limit: u32) -> Vec<u64>;
+    fn preview_vesting_schedule(e: Env, params: types::LockupInput) -> Vec<(u64, i128)>;
     fn create_stream(e: Env, params: types::LockupInput) -> Result<u64, errors::CustomErrors>;
+    fn create_stream_by_rate(
+        e: Env,
+        params: types::LockupInput,
+        per_period: i128,
+    ) -> Result<u64, errors::CustomErrors>;
+    fn create_group_stream(
+        e: Env,
+        params: types::LockupInput,
+        receivers: Vec<(Address, u32)>,
+    ) -> Result<u64, errors::CustomErrors>;
     fn create_vesting(e: Env, params: types::LockupInput) -> Result<u64, errors::CustomErrors>;
-    fn cancel_lockup(e: Env, id: u64) -> Result<(i128, i128), errors::CustomErrors>;
+    fn create_tranched_vesting(
+        e: Env,
+        params: types::LockupInput,
+        tranches: Vec<(u64, i128)>,
+    ) -> Result<u64, errors::CustomErrors>;
+    fn cancel_lockup(
+        e: Env,
+        id: u64,
+        push_to_receiver: bool,
+    ) -> Result<(i128, i128), errors::CustomErrors>;
+    fn cancel_lockup_with_nonce(
+        e: Env,
+        id: u64,
+        push_to_receiver: bool,
+        nonce: u64,
+    ) -> Result<(i128, i128), errors::CustomErrors>;
+    fn settle_lockup(
+        e: Env,
+        id: u64,
+        to_receiver: i128,
+    ) -> Result<(i128, i128), errors::CustomErrors>;
+    fn renew_stream(
+        e: Env,
+        id: u64,
+        new_params: types::LockupInput,
+    ) -> Result<u64, errors::CustomErrors>;
+    fn cancel_lockups(
+        e: Env,
+        sender: Address,
+        ids: Vec<u64>,
+        strict: bool,
+    ) -> Result<Vec<types::CancellationResult>, errors::CustomErrors>;
+    fn can_cancel(e: Env, id: u64, who: Address) -> bool;
+    fn get_refundable_to_sender(e: Env, id: u64) -> Result<i128, errors::CustomErrors>;
     fn withdraw_lockup(e: Env, id: u64, amount: i128) -> Result<i128, errors::CustomErrors>;
+    fn withdraw_lockup_with_nonce(
+        e: Env,
+        id: u64,
+        amount: i128,
+        nonce: u64,
+    ) -> Result<i128, errors::CustomErrors>;
+    fn withdraw_all(e: Env, receiver: Address, ids: Vec<u64>)
+        -> Result<i128, errors::CustomErrors>;
+    fn withdraw_group_share(
+        e: Env,
+        id: u64,
+        member: Address,
+        amount: i128,
+    ) -> Result<i128, errors::CustomErrors>;
+    fn get_group_shares(e: Env, id: u64) -> Option<Vec<(Address, u32)>>;
+    fn get_group_withdrawn(e: Env, id: u64, member: Address) -> i128;
+    fn simulate_withdraw(e: Env, id: u64, amount: i128) -> Result<i128, errors::CustomErrors>;
+    fn can_withdraw(e: Env, id: u64, who: Address) -> bool;
+    fn set_default_destination(
+        e: Env,
+        id: u64,
+        destination: Address,
+    ) -> Result<(), errors::CustomErrors>;
+    fn clear_default_destination(e: Env, id: u64) -> Result<(), errors::CustomErrors>;
+    fn get_default_destination(e: Env, id: u64) -> Option<Address>;
+    fn transfer_lockup_receiver(
+        e: Env,
+        id: u64,
+        new_receiver: Address,
+    ) -> Result<(), errors::CustomErrors>;
     fn topup_lockup(e: Env, id: u64, amount: i128) -> Result<i128, errors::CustomErrors>;
+    fn accelerate_lockup(e: Env, id: u64, new_end_date: u64) -> Result<(), errors::CustomErrors>;
+    fn change_lockup_token(e: Env, id: u64, new_token: Address)
+        -> Result<(), errors::CustomErrors>;
+    fn fund_lockup(e: Env, id: u64, amount: i128) -> Result<i128, errors::CustomErrors>;
+    fn get_lockup_funding(e: Env, id: u64) -> Result<(i128, i128), errors::CustomErrors>;
+    fn deposit(
+        e: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, errors::CustomErrors>;
+    fn withdraw_pool(
+        e: Env,
+        sender: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, errors::CustomErrors>;
+    fn get_sender_pool(e: Env, sender: Address, token: Address) -> i128;
+    fn register_template(
+        e: Env,
+        template: types::LockupTemplate,
+    ) -> Result<u64, errors::CustomErrors>;
+    fn get_template(e: Env, id: u64) -> Result<types::LockupTemplate, errors::CustomErrors>;
+    fn create_stream_from_template(
+        e: Env,
+        template_id: u64,
+        receiver: Address,
+        amount: i128,
+        start_date: u64,
+        end_date: u64,
+    ) -> Result<u64, errors::CustomErrors>;
+    fn bump_lockups_ttl(e: Env, ids: Vec<u64>) -> Result<u32, errors::CustomErrors>;
+    fn get_lockup_storage_info(e: Env, id: u64) -> Result<(u32, u32), errors::CustomErrors>;
+    fn sweep_token(e: Env, token: Address, to: Address) -> Result<i128, errors::CustomErrors>;
+    fn get_locked_balance(e: Env, token: Address) -> i128;
 }
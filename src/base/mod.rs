@@ -3,7 +3,10 @@ pub mod data_key;
 pub mod errors;
 pub mod events;
 pub mod extend_ttl;
+pub mod fee_strategy;
 pub mod interface;
+pub mod position_nft;
+pub mod release_condition;
 pub mod storage;
 pub mod token;
 pub mod types;
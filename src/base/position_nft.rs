@@ -0,0 +1,18 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The interface of the companion NFT contract representing tokenized lockups,
+/// the token id of a position is the id of its lockup
+#[allow(dead_code)]
+#[contractclient(name = "PositionNftClient")]
+pub trait PositionNft {
+    fn mint(e: Env, to: Address, token_id: u64);
+    fn owner_of(e: Env, token_id: u64) -> Address;
+}
+
+pub fn mint(e: &Env, nft: &Address, to: &Address, token_id: u64) {
+    PositionNftClient::new(e, nft).mint(to, &token_id);
+}
+
+pub fn owner_of(e: &Env, nft: &Address, token_id: u64) -> Address {
+    PositionNftClient::new(e, nft).owner_of(&token_id)
+}
@@ -0,0 +1,13 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// The interface of an external contract attesting that the condition of a
+/// conditional lockup was met, withdrawals are blocked until it returns true
+#[allow(dead_code)]
+#[contractclient(name = "ReleaseConditionClient")]
+pub trait ReleaseCondition {
+    fn is_released(e: Env, id: u64) -> bool;
+}
+
+pub fn is_released(e: &Env, condition: &Address, id: u64) -> bool {
+    ReleaseConditionClient::new(e, condition).is_released(&id)
+}
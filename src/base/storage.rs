@@ -1,4 +1,4 @@
-use soroban_sdk::Env;
+use soroban_sdk::{vec, Address, BytesN, Env, TryFromVal, Val, Vec};
 
 use super::data_key;
 use super::errors;
@@ -6,36 +6,789 @@ use super::extend_ttl::{extend_contract_ttl, extend_data_ttl};
 use super::types;
 
 pub fn get_lockup_by_id(e: &Env, id: &u64) -> Result<types::Lockup, errors::CustomErrors> {
-    match e
+    let value: Option<Val> = e
         .storage()
         .persistent()
-        .get(&data_key::DataKey::Lockup(*id))
-    {
+        .get(&data_key::DataKey::Lockup(*id));
+
+    match value {
         None => Err(errors::CustomErrors::LockupNotFound),
-        Some(stream) => Ok(stream),
+        Some(value) => decode_lockup(e, &value).ok_or(errors::CustomErrors::LockupUndecodable),
     }
 }
 
-pub fn get_latest_lockup_id(e: &Env) -> u64 {
+/// Reads a lockup without trapping, entries which are missing or can't be decoded
+/// are returned as `None`
+pub fn try_get_lockup(e: &Env, id: u64) -> Option<types::Lockup> {
+    let value: Val = e
+        .storage()
+        .persistent()
+        .get(&data_key::DataKey::Lockup(id))?;
+
+    decode_lockup(e, &value)
+}
+
+/// Decodes a lockup stored in the current layout or in an older one, which is
+/// upgraded with the defaults of the fields added since. Entries are tagged with
+/// their layout version, except the ones stored before versioning
+fn decode_lockup(e: &Env, value: &Val) -> Option<types::Lockup> {
+    if let Ok(stored) = types::StoredLockup::try_from_val(e, value) {
+        return match stored {
+            types::StoredLockup::V2(lockup) => Some(lockup),
+        };
+    }
+
+    types::LockupV1::try_from_val(e, value)
+        .ok()
+        .map(types::Lockup::from)
+}
+
+/// Lockups stored in the legacy layout predate the locked balance, so what they
+/// still hold is added to it the first time they are rewritten
+fn count_legacy_lockup(e: &Env, key: &data_key::DataKey) {
+    let Some(value) = e.storage().persistent().get::<_, Val>(key) else {
+        return;
+    };
+
+    if types::StoredLockup::try_from_val(e, &value).is_ok() {
+        return;
+    }
+
+    if let Ok(lockup) = types::LockupV1::try_from_val(e, &value) {
+        if !lockup.is_cancelled {
+            increase_locked_balance(e, &lockup.token, &(lockup.amount - lockup.withdrawn));
+        }
+    }
+}
+
+/// Legacy contracts stored the next lockup id in `LatestLockupId`, every id below it
+/// may hold a lockup stored before the locked balance was kept
+pub fn get_legacy_lockup_count(e: &Env) -> u64 {
     e.storage()
         .instance()
         .get(&data_key::DataKey::LatestLockupId)
         .unwrap_or(0)
 }
 
-pub fn increment_latest_lockup_id(e: &Env, id: &u64) {
+/// Returns the next legacy lockup id `migrate_lockups` rewrites
+pub fn get_legacy_migration_cursor(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&data_key::DataKey::LegacyMigrationCursor)
+        .unwrap_or(0)
+}
+
+pub fn set_legacy_migration_cursor(e: &Env, cursor: &u64) {
     e.storage()
         .instance()
-        .set(&data_key::DataKey::LatestLockupId, &(id + 1));
+        .set(&data_key::DataKey::LegacyMigrationCursor, cursor);
 
-    extend_contract_ttl(&e);
+    extend_contract_ttl(e);
+}
+
+/// The locked balance only covers every lockup once all the legacy ones are migrated
+pub fn is_legacy_migration_complete(e: &Env) -> bool {
+    get_legacy_migration_cursor(e) >= get_legacy_lockup_count(e)
+}
+
+/// Extends the TTL of a lockup, returns false if it doesn't exist
+pub fn extend_lockup_ttl(e: &Env, id: u64) -> bool {
+    let key = data_key::DataKey::Lockup(id);
+
+    if !e.storage().persistent().has(&key) {
+        return false;
+    }
+
+    extend_data_ttl(e, &key);
+
+    true
+}
+
+/// Lockup ids start at 1, so a latest id of 0 means no lockup has been created yet.
+/// Ids used to start at 0 and `LatestLockupId` held the next id rather than the
+/// latest one, contracts which still have it stored have a lockup 0
+pub fn get_latest_lockup_id(e: &Env) -> u64 {
+    if let Some(id) = e.storage().instance().get(&data_key::DataKey::LastLockupId) {
+        return id;
+    }
+
+    e.storage()
+        .instance()
+        .get::<_, u64>(&data_key::DataKey::LatestLockupId)
+        .map_or(0, |next_id| next_id.saturating_sub(1))
+}
+
+pub fn set_latest_lockup_id(e: &Env, id: &u64) {
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::LastLockupId, id);
+
+    extend_contract_ttl(e);
+}
+
+pub fn get_active_count(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&data_key::DataKey::ActiveCount)
+        .unwrap_or(0)
+}
+
+pub fn increase_active_count(e: &Env) {
+    let count = get_active_count(e);
+
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::ActiveCount, &(count + 1));
+
+    extend_contract_ttl(e);
+}
+
+/// Lockups created before the count was kept are settled without having been
+/// counted, so the count saturates at 0
+pub fn decrease_active_count(e: &Env) {
+    let count = get_active_count(e);
+
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::ActiveCount, &count.saturating_sub(1));
+
+    extend_contract_ttl(e);
+}
+
+/// Returns the sequence number for the next event and increments it, so consumers
+/// can detect gaps or duplicates in the emitted events
+pub fn next_event_sequence(e: &Env) -> u64 {
+    let sequence: u64 = e
+        .storage()
+        .instance()
+        .get(&data_key::DataKey::EventSequence)
+        .unwrap_or(0);
+
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::EventSequence, &(sequence + 1));
+
+    extend_contract_ttl(e);
+
+    sequence
 }
 
 pub fn set_lockup(e: &Env, id: u64, stream: &types::Lockup) {
     let key = data_key::DataKey::Lockup(id);
 
-    e.storage().persistent().set(&key, stream);
+    count_legacy_lockup(e, &key);
+
+    e.storage()
+        .persistent()
+        .set(&key, &types::StoredLockup::V2(stream.clone()));
+
+    extend_data_ttl(e, &key);
+    extend_contract_ttl(e);
+}
+
+/// Returns at most `count` ids of the receiver's lockups starting at the position
+/// `start` of the index
+pub fn get_receiver_lockup_ids(e: &Env, receiver: &Address, start: u32, count: u32) -> Vec<u64> {
+    get_index_ids(
+        e,
+        &data_key::LockupIndex::Receiver(receiver.clone()),
+        start,
+        count,
+    )
+}
+
+pub fn get_receiver_lockup_count(e: &Env, receiver: &Address) -> u32 {
+    get_index_len(e, &data_key::LockupIndex::Receiver(receiver.clone()))
+}
+
+pub fn add_receiver_lockup_id(e: &Env, receiver: &Address, id: u64) {
+    push_index_id(e, &data_key::LockupIndex::Receiver(receiver.clone()), id);
+}
+
+/// Returns at most `count` ids of the sender's lockups starting at the position
+/// `start` of the index
+pub fn get_sender_lockup_ids(e: &Env, sender: &Address, start: u32, count: u32) -> Vec<u64> {
+    get_index_ids(
+        e,
+        &data_key::LockupIndex::Sender(sender.clone()),
+        start,
+        count,
+    )
+}
+
+pub fn get_sender_lockup_count(e: &Env, sender: &Address) -> u32 {
+    get_index_len(e, &data_key::LockupIndex::Sender(sender.clone()))
+}
+
+pub fn add_sender_lockup_id(e: &Env, sender: &Address, id: u64) {
+    push_index_id(e, &data_key::LockupIndex::Sender(sender.clone()), id);
+}
+
+/// Removes a lockup from the receiver's index by moving the last id of the index
+/// in its place, so the order of the index isn't kept
+pub fn remove_receiver_lockup_id(e: &Env, receiver: &Address, id: u64) {
+    let index = data_key::LockupIndex::Receiver(receiver.clone());
+    let len = get_writable_index_len(e, &index);
+
+    let position_key = data_key::DataKey::ReceiverLockupPosition(id);
+    let Some(position) = e.storage().persistent().get::<_, u32>(&position_key) else {
+        return;
+    };
+
+    let last_chunk = (len - 1) / INDEX_CHUNK_SIZE;
+    let mut last_ids = get_index_chunk(e, &index, last_chunk);
+    let last_id = last_ids.pop_back_unchecked();
+
+    set_index_chunk(e, &index, last_chunk, &last_ids);
+    set_index_len(e, &index, len - 1);
+
+    if last_id != id {
+        let chunk = position / INDEX_CHUNK_SIZE;
+        let mut ids = get_index_chunk(e, &index, chunk);
+
+        ids.set(position % INDEX_CHUNK_SIZE, last_id);
+
+        set_index_chunk(e, &index, chunk, &ids);
+        set_index_position(e, &index, position, last_id);
+    }
+
+    e.storage().persistent().remove(&position_key);
+}
+
+const INDEX_CHUNK_SIZE: u32 = 50;
+
+fn get_index_len(e: &Env, index: &data_key::LockupIndex) -> u32 {
+    match e
+        .storage()
+        .persistent()
+        .get(&data_key::DataKey::LockupIndexLen(index.clone()))
+    {
+        Some(len) => len,
+        None => get_legacy_index(e, index).map_or(0, |ids| ids.len()),
+    }
+}
+
+/// Returns the length of an index about to be written to, an index still in its
+/// legacy list is moved to chunks first
+fn get_writable_index_len(e: &Env, index: &data_key::LockupIndex) -> u32 {
+    match e
+        .storage()
+        .persistent()
+        .get(&data_key::DataKey::LockupIndexLen(index.clone()))
+    {
+        Some(len) => len,
+        None => migrate_legacy_index(e, index),
+    }
+}
+
+fn set_index_len(e: &Env, index: &data_key::LockupIndex, len: u32) {
+    let key = data_key::DataKey::LockupIndexLen(index.clone());
+
+    e.storage().persistent().set(&key, &len);
+
+    extend_data_ttl(e, &key);
+}
+
+fn get_index_chunk(e: &Env, index: &data_key::LockupIndex, chunk: u32) -> Vec<u64> {
+    e.storage()
+        .persistent()
+        .get(&data_key::DataKey::LockupIndexChunk(index.clone(), chunk))
+        .unwrap_or(vec![e])
+}
+
+fn set_index_chunk(e: &Env, index: &data_key::LockupIndex, chunk: u32, ids: &Vec<u64>) {
+    let key = data_key::DataKey::LockupIndexChunk(index.clone(), chunk);
+
+    if ids.is_empty() {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+
+    e.storage().persistent().set(&key, ids);
+
+    extend_data_ttl(e, &key);
+}
+
+fn get_index_ids(e: &Env, index: &data_key::LockupIndex, start: u32, count: u32) -> Vec<u64> {
+    let len = get_index_len(e, index);
+    let end = start.saturating_add(count).min(len);
+
+    if start >= end {
+        return vec![e];
+    }
+
+    if let Some(ids) = get_legacy_index(e, index) {
+        return ids.slice(start..end);
+    }
+
+    let mut ids = vec![e];
+    let mut position = start;
+
+    while position < end {
+        let offset = position % INDEX_CHUNK_SIZE;
+        let count = (end - position).min(INDEX_CHUNK_SIZE - offset);
+        let chunk = get_index_chunk(e, index, position / INDEX_CHUNK_SIZE);
+
+        ids.append(&chunk.slice(offset..offset + count));
+
+        position += count;
+    }
+
+    ids
+}
+
+fn push_index_id(e: &Env, index: &data_key::LockupIndex, id: u64) {
+    let position = get_writable_index_len(e, index);
+    let chunk = position / INDEX_CHUNK_SIZE;
+    let mut ids = get_index_chunk(e, index, chunk);
+
+    ids.push_back(id);
+
+    set_index_chunk(e, index, chunk, &ids);
+    set_index_len(e, index, position + 1);
+    set_index_position(e, index, position, id);
+}
+
+/// Receivers can change, so the position of each lockup in their index is kept to
+/// remove it. Senders never change
+fn set_index_position(e: &Env, index: &data_key::LockupIndex, position: u32, id: u64) {
+    if let data_key::LockupIndex::Receiver(_) = index {
+        let key = data_key::DataKey::ReceiverLockupPosition(id);
+
+        e.storage().persistent().set(&key, &position);
+
+        extend_data_ttl(e, &key);
+    }
+}
+
+/// Indexes were a single list before being chunked
+fn legacy_index_key(index: &data_key::LockupIndex) -> data_key::DataKey {
+    match index {
+        data_key::LockupIndex::Receiver(receiver) => {
+            data_key::DataKey::ReceiverLockups(receiver.clone())
+        }
+        data_key::LockupIndex::Sender(sender) => data_key::DataKey::SenderLockups(sender.clone()),
+    }
+}
+
+fn get_legacy_index(e: &Env, index: &data_key::LockupIndex) -> Option<Vec<u64>> {
+    e.storage().persistent().get(&legacy_index_key(index))
+}
+
+/// Moves an index from its legacy list to chunks and returns its length
+fn migrate_legacy_index(e: &Env, index: &data_key::LockupIndex) -> u32 {
+    let Some(ids) = get_legacy_index(e, index) else {
+        return 0;
+    };
+
+    e.storage().persistent().remove(&legacy_index_key(index));
+
+    let len = ids.len();
+    let mut chunk = 0;
+
+    while chunk * INDEX_CHUNK_SIZE < len {
+        let start = chunk * INDEX_CHUNK_SIZE;
+
+        set_index_chunk(
+            e,
+            index,
+            chunk,
+            &ids.slice(start..(start + INDEX_CHUNK_SIZE).min(len)),
+        );
+
+        chunk += 1;
+    }
+
+    for (position, id) in ids.iter().enumerate() {
+        set_index_position(e, index, position as u32, id);
+    }
+
+    set_index_len(e, index, len);
+
+    len
+}
+
+/// Returns the amount withdrawn by an earlier withdrawal with the same nonce
+pub fn get_withdraw_nonce_result(e: &Env, id: u64, nonce: u64) -> Option<i128> {
+    let results: Vec<(u64, i128)> = e
+        .storage()
+        .persistent()
+        .get(&data_key::DataKey::WithdrawNonces(id))
+        .unwrap_or(vec![e]);
+
+    results
+        .iter()
+        .find(|(stored_nonce, _)| *stored_nonce == nonce)
+        .map(|(_, amount)| amount)
+}
+
+/// Records the result of a withdrawal, only the latest `max_nonces` are kept
+pub fn record_withdraw_nonce(e: &Env, id: u64, nonce: u64, amount: i128, max_nonces: u32) {
+    let key = data_key::DataKey::WithdrawNonces(id);
+    let mut results: Vec<(u64, i128)> = e.storage().persistent().get(&key).unwrap_or(vec![e]);
+
+    results.push_back((nonce, amount));
+
+    while results.len() > max_nonces {
+        results.pop_front();
+    }
+
+    e.storage().persistent().set(&key, &results);
+
+    extend_data_ttl(e, &key);
+}
+
+pub fn get_default_destination(e: &Env, id: u64) -> Option<Address> {
+    e.storage()
+        .persistent()
+        .get(&data_key::DataKey::DefaultDestination(id))
+}
+
+pub fn set_default_destination(e: &Env, id: u64, destination: &Address) {
+    let key = data_key::DataKey::DefaultDestination(id);
+
+    e.storage().persistent().set(&key, destination);
+
+    extend_data_ttl(e, &key);
+}
+
+pub fn remove_default_destination(e: &Env, id: u64) {
+    e.storage()
+        .persistent()
+        .remove(&data_key::DataKey::DefaultDestination(id));
+}
+
+/// Returns the members of a group lockup and their shares, `None` for other lockups
+pub fn get_group_shares(e: &Env, id: u64) -> Option<Vec<(Address, u32)>> {
+    e.storage()
+        .persistent()
+        .get(&data_key::DataKey::GroupShares(id))
+}
+
+pub fn set_group_shares(e: &Env, id: u64, shares: &Vec<(Address, u32)>) {
+    let key = data_key::DataKey::GroupShares(id);
+
+    e.storage().persistent().set(&key, shares);
+
+    extend_data_ttl(e, &key);
+}
+
+pub fn get_group_withdrawn(e: &Env, id: u64, member: &Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&data_key::DataKey::GroupWithdrawn(id, member.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_group_withdrawn(e: &Env, id: u64, member: &Address, withdrawn: &i128) {
+    let key = data_key::DataKey::GroupWithdrawn(id, member.clone());
+
+    e.storage().persistent().set(&key, withdrawn);
+
+    extend_data_ttl(e, &key);
+}
+
+/// Returns the amounts of the cancellation if it was made with the given nonce
+pub fn get_cancel_nonce_result(e: &Env, id: u64, nonce: u64) -> Option<(i128, i128)> {
+    let result: Option<(u64, i128, i128)> = e
+        .storage()
+        .persistent()
+        .get(&data_key::DataKey::CancelNonce(id));
+
+    match result {
+        Some((stored_nonce, sender_amount, receiver_amount)) if stored_nonce == nonce => {
+            Some((sender_amount, receiver_amount))
+        }
+        _ => None,
+    }
+}
+
+pub fn record_cancel_nonce(e: &Env, id: u64, nonce: u64, amounts: (i128, i128)) {
+    let key = data_key::DataKey::CancelNonce(id);
+
+    e.storage()
+        .persistent()
+        .set(&key, &(nonce, amounts.0, amounts.1));
+
+    extend_data_ttl(e, &key);
+}
+
+pub fn get_lockup_id_by_external_ref(e: &Env, external_ref: &BytesN<32>) -> Option<u64> {
+    e.storage()
+        .persistent()
+        .get(&data_key::DataKey::ExternalRef(external_ref.clone()))
+}
+
+pub fn set_external_ref(e: &Env, external_ref: &BytesN<32>, id: u64) {
+    let key = data_key::DataKey::ExternalRef(external_ref.clone());
+
+    e.storage().persistent().set(&key, &id);
+
+    extend_data_ttl(e, &key);
+}
+
+pub fn has_admin(e: &Env) -> bool {
+    e.storage().instance().has(&data_key::DataKey::Admin)
+}
+
+pub fn get_admin(e: &Env) -> Result<Address, errors::CustomErrors> {
+    match e.storage().instance().get(&data_key::DataKey::Admin) {
+        None => Err(errors::CustomErrors::NotInitialized),
+        Some(admin) => Ok(admin),
+    }
+}
+
+pub fn set_admin(e: &Env, admin: &Address) {
+    e.storage().instance().set(&data_key::DataKey::Admin, admin);
+
+    extend_contract_ttl(e);
+}
+
+pub fn get_fee_config(e: &Env) -> Option<types::FeeConfig> {
+    e.storage().instance().get(&data_key::DataKey::FeeConfig)
+}
+
+pub fn set_fee_config(e: &Env, config: &types::FeeConfig) {
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::FeeConfig, config);
+
+    extend_contract_ttl(e);
+}
+
+pub fn get_position_nft(e: &Env) -> Option<Address> {
+    e.storage().instance().get(&data_key::DataKey::PositionNft)
+}
+
+pub fn set_position_nft(e: &Env, nft: &Address) {
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::PositionNft, nft);
+
+    extend_contract_ttl(e);
+}
+
+pub fn get_min_duration(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&data_key::DataKey::MinDuration)
+        .unwrap_or(0)
+}
+
+pub fn set_min_duration(e: &Env, min_duration: &u64) {
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::MinDuration, min_duration);
+
+    extend_contract_ttl(e);
+}
+
+pub fn get_default_rate(e: &Env) -> Option<types::Rate> {
+    e.storage().instance().get(&data_key::DataKey::DefaultRate)
+}
+
+pub fn set_default_rate(e: &Env, rate: &types::Rate) {
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::DefaultRate, rate);
+
+    extend_contract_ttl(e);
+}
+
+pub fn get_withdrawal_buckets(e: &Env) -> Option<types::WithdrawalBuckets> {
+    e.storage()
+        .instance()
+        .get(&data_key::DataKey::WithdrawalBuckets)
+}
+
+pub fn set_withdrawal_buckets(e: &Env, buckets: &types::WithdrawalBuckets) {
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::WithdrawalBuckets, buckets);
+
+    extend_contract_ttl(e);
+}
+
+pub fn get_min_withdraw(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&data_key::DataKey::MinWithdraw)
+        .unwrap_or(0)
+}
+
+pub fn set_min_withdraw(e: &Env, min_withdraw: &i128) {
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::MinWithdraw, min_withdraw);
+
+    extend_contract_ttl(e);
+}
+
+pub fn get_max_duration(e: &Env) -> Option<u64> {
+    e.storage().instance().get(&data_key::DataKey::MaxDuration)
+}
+
+pub fn set_max_duration(e: &Env, max_duration: &u64) {
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::MaxDuration, max_duration);
+
+    extend_contract_ttl(e);
+}
+
+pub fn get_dormancy_period(e: &Env) -> Option<u64> {
+    e.storage()
+        .instance()
+        .get(&data_key::DataKey::DormancyPeriod)
+}
+
+pub fn set_dormancy_period(e: &Env, period: &u64) {
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::DormancyPeriod, period);
+
+    extend_contract_ttl(e);
+}
+
+/// Sets the reentrancy lock around the token calls of withdrawals and cancellations,
+/// fails if it's already set. It has to be taken after the last fallible step, as
+/// batch cancellations don't roll back the items that failed
+pub fn acquire_reentrancy_lock(e: &Env) -> Result<(), errors::CustomErrors> {
+    let key = data_key::DataKey::ReentrancyLock;
+
+    if e.storage().instance().has(&key) {
+        return Err(errors::CustomErrors::Reentrancy);
+    }
+
+    e.storage().instance().set(&key, &true);
+
+    Ok(())
+}
+
+pub fn release_reentrancy_lock(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&data_key::DataKey::ReentrancyLock);
+}
+
+pub fn get_locked_balance(e: &Env, token: &Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&data_key::DataKey::LockedBalance(token.clone()))
+        .unwrap_or(0)
+}
+
+pub fn increase_locked_balance(e: &Env, token: &Address, amount: &i128) {
+    let key = data_key::DataKey::LockedBalance(token.clone());
+    let balance = get_locked_balance(e, token);
+
+    e.storage().persistent().set(&key, &(balance + amount));
+
+    extend_data_ttl(e, &key);
+}
+
+pub fn get_fees_collected(e: &Env, token: &Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&data_key::DataKey::FeesCollected(token.clone()))
+        .unwrap_or(0)
+}
+
+pub fn increase_fees_collected(e: &Env, token: &Address, amount: &i128) {
+    let key = data_key::DataKey::FeesCollected(token.clone());
+    let collected = get_fees_collected(e, token);
+
+    e.storage().persistent().set(&key, &(collected + amount));
+
+    extend_data_ttl(e, &key);
+}
+
+/// Floored at zero, so a miscount can't make the contract look like it holds a
+/// surplus which could be swept
+pub fn decrease_locked_balance(e: &Env, token: &Address, amount: &i128) {
+    let key = data_key::DataKey::LockedBalance(token.clone());
+    let balance = get_locked_balance(e, token);
+
+    e.storage()
+        .persistent()
+        .set(&key, &(balance - amount).max(0));
+
+    extend_data_ttl(e, &key);
+}
+
+pub fn get_sender_pool(e: &Env, sender: &Address, token: &Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&data_key::DataKey::SenderPool(
+            sender.clone(),
+            token.clone(),
+        ))
+        .unwrap_or(0)
+}
+
+pub fn increase_sender_pool(e: &Env, sender: &Address, token: &Address, amount: &i128) {
+    let key = data_key::DataKey::SenderPool(sender.clone(), token.clone());
+    let balance = get_sender_pool(e, sender, token);
+
+    e.storage().persistent().set(&key, &(balance + amount));
+
+    extend_data_ttl(e, &key);
+}
+
+pub fn decrease_sender_pool(
+    e: &Env,
+    sender: &Address,
+    token: &Address,
+    amount: &i128,
+) -> Result<(), errors::CustomErrors> {
+    let key = data_key::DataKey::SenderPool(sender.clone(), token.clone());
+    let balance = get_sender_pool(e, sender, token);
+
+    if *amount > balance {
+        return Err(errors::CustomErrors::InsufficientPoolBalance);
+    }
+
+    e.storage().persistent().set(&key, &(balance - amount));
+
+    extend_data_ttl(e, &key);
+
+    Ok(())
+}
+
+pub fn get_template_by_id(
+    e: &Env,
+    id: &u64,
+) -> Result<types::LockupTemplate, errors::CustomErrors> {
+    match e
+        .storage()
+        .persistent()
+        .get(&data_key::DataKey::Template(*id))
+    {
+        None => Err(errors::CustomErrors::TemplateNotFound),
+        Some(template) => Ok(template),
+    }
+}
+
+pub fn get_latest_template_id(e: &Env) -> u64 {
+    e.storage()
+        .instance()
+        .get(&data_key::DataKey::LatestTemplateId)
+        .unwrap_or(0)
+}
+
+pub fn increment_latest_template_id(e: &Env, id: &u64) {
+    e.storage()
+        .instance()
+        .set(&data_key::DataKey::LatestTemplateId, &(id + 1));
+
+    extend_contract_ttl(e);
+}
+
+pub fn set_template(e: &Env, id: u64, template: &types::LockupTemplate) {
+    let key = data_key::DataKey::Template(id);
+
+    e.storage().persistent().set(&key, template);
 
-    extend_data_ttl(&e, &key);
-    extend_contract_ttl(&e);
+    extend_data_ttl(e, &key);
+    extend_contract_ttl(e);
 }
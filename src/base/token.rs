@@ -1,14 +1,30 @@
 use soroban_sdk::{token::Client, Address, Env};
 
 pub fn transfer(e: &Env, token: &Address, to: &Address, amount: &i128) {
-    Client::new(&e, &token).transfer(&e.current_contract_address(), &to, &amount);
+    Client::new(e, token).transfer(&e.current_contract_address(), to, amount);
 }
 
 pub fn transfer_from(e: &Env, token: &Address, from: &Address, amount: &i128) {
-    Client::new(&e, &token).transfer_from(
+    Client::new(e, token).transfer_from(
         &e.current_contract_address(),
-        &from,
+        from,
         &e.current_contract_address(),
-        &amount,
+        amount,
     );
 }
+
+pub fn transfer_from_to(e: &Env, token: &Address, from: &Address, to: &Address, amount: &i128) {
+    Client::new(e, token).transfer_from(&e.current_contract_address(), from, to, amount);
+}
+
+pub fn allowance(e: &Env, token: &Address, from: &Address) -> i128 {
+    Client::new(e, token).allowance(from, &e.current_contract_address())
+}
+
+pub fn decimals(e: &Env, token: &Address) -> u32 {
+    Client::new(e, token).decimals()
+}
+
+pub fn balance(e: &Env, token: &Address, of: &Address) -> i128 {
+    Client::new(e, token).balance(of)
+}
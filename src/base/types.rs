@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
 
 #[contracttype]
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -11,12 +11,185 @@ pub enum Rate {
     Annually = 31536000,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[contracttype]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LockupKind {
+    Linear,
+    Vesting,
+    Tranched,
+}
+
+#[contracttype]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LockupStatus {
+    NotStarted,
+    Active,
+    Ended,
+    Settled,
+    Cancelled,
+}
+
+/// The bits of `get_features`, each set when the optional feature is configured.
+/// Templates need no configuration, so their bit is always set
+pub const FEATURE_FEES: u32 = 1 << 0;
+pub const FEATURE_TEMPLATES: u32 = 1 << 1;
+pub const FEATURE_TOKENIZED_POSITIONS: u32 = 1 << 2;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Amounts {
     pub sender_amount: i128,
     pub receiver_amount: i128,
 }
 
+#[contracttype]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum CliffMode {
+    /// The cliff only gates withdrawals, the amount accrues since the start date
+    #[default]
+    GateFromStart,
+    /// Nothing accrues before the cliff, then the amount accrues linearly until the end
+    LinearFromCliff,
+}
+
+#[contracttype]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum StreamMode {
+    /// The elapsed part of the amount goes to the receiver
+    #[default]
+    Forward,
+    /// The elapsed part of the amount goes back to the sender, so the receiver has to
+    /// claim their part early. What the receiver has claimed stays theirs
+    Reverse,
+}
+
+/// An optional external reference, `Option<BytesN<32>>` can't be used as a
+/// contract type field
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ExternalRef {
+    #[default]
+    None,
+    Some(BytesN<32>),
+}
+
+/// The token fees are charged in, `Option<Address>` can't be used as a contract
+/// type field
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FeeToken {
+    /// The fee is skimmed from the lockup amount in the lockup token
+    #[default]
+    None,
+    /// The fee is pulled separately in the given token
+    Some(Address),
+}
+
+/// The settings applied by `initialize` in the same call as setting the admin
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractConfig {
+    /// The creation fee in basis points, no fee config is set when it's 0
+    pub fee_bps: u32,
+    pub fee_recipient: Address,
+    /// The minimum duration in seconds of new lockups
+    pub min_duration: u64,
+}
+
+/// The thresholds withdrawals are classified with in the metrics events, amounts
+/// below `medium_from` are small and amounts from `large_from` are large
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithdrawalBuckets {
+    pub medium_from: i128,
+    pub large_from: i128,
+}
+
+/// The contract computing creation fees, `Option<Address>` can't be used as a
+/// contract type field
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FeeStrategy {
+    /// The fee is `fee_bps` of the lockup amount
+    #[default]
+    None,
+    /// The fee is computed by the given contract
+    Some(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeeConfig {
+    /// The creation fee in basis points of the lockup amount
+    pub fee_bps: u32,
+    pub fee_recipient: Address,
+    pub fee_token: FeeToken,
+    /// The cancellation fee in basis points of the sender's refund, always paid
+    /// in the lockup token
+    pub cancel_fee_bps: u32,
+    pub fee_strategy: FeeStrategy,
+}
+
+/// The NFT representing a tokenized lockup, `Option<Address>` can't be used as a
+/// contract type field
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Position {
+    #[default]
+    None,
+    /// The NFT contract and the token id
+    Tokenized(Address, u64),
+}
+
+/// The contract releasing a conditional lockup, `Option<Address>` can't be used as
+/// a contract type field
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Condition {
+    #[default]
+    None,
+    /// Withdrawals are blocked until the contract reports the lockup as released
+    Some(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LockupOptions {
+    pub initial_deposit: Option<i128>,
+    pub external_ref: ExternalRef,
+    /// Draws the deposit from the sender's pool instead of an allowance
+    pub from_pool: bool,
+    /// Withdrawals are rejected before this date even if something is vested
+    pub withdraw_unlock_date: Option<u64>,
+    /// Mints an NFT of the position to the receiver, the receiver then follows
+    /// the owner of the NFT
+    pub tokenized: bool,
+    /// How streams accrue around the cliff, vestings ignore it
+    pub cliff_mode: CliffMode,
+    /// Rejects a cliff on the end date, which would unlock everything at once
+    pub strict_schedule: bool,
+    /// Blocks withdrawals until an external contract releases the lockup
+    pub condition: Condition,
+    /// Steps vestings on the multiples of the rate since timestamp 0 instead of
+    /// from the start date, streams ignore it
+    pub align_to_epoch: bool,
+    /// Set to false to skip the withdrawn events of the lockup, creation and
+    /// cancellation events are always emitted. Defaults to true
+    pub emit_events: Option<bool>,
+    /// Set to false to keep the receiver from transferring the lockup to another
+    /// receiver. Defaults to true
+    pub receiver_transferable: Option<bool>,
+    /// Schedules the stream in ledger sequences instead of timestamps, all of its
+    /// dates are then ledger sequences. Vestings can't use it since their rates are
+    /// periods of time
+    pub use_ledger_seq: bool,
+    /// Which party the elapsed part of a stream goes to, vestings can't be reversed
+    /// and reversed streams can't have a cliff
+    pub mode: StreamMode,
+    /// Stores the admin's default rate in place of the given one when the admin has
+    /// set one, only `create_stream` reads it
+    pub use_default_rate: bool,
+}
+
 #[contracttype]
 #[derive(Debug)]
 pub struct LockupInput {
@@ -29,12 +202,27 @@ pub struct LockupInput {
     pub start_date: u64,
     pub end_date: u64,
     pub rate: Rate,
+    pub options: LockupOptions,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LockupTemplate {
+    pub sender: Address,
+    pub token: Address,
+    pub rate: Rate,
+    pub cliff_duration: u64,
+    pub cancellable: bool,
 }
 
 #[contracttype]
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Lockup {
     pub withdrawn: i128,
+    /// What was sent back to the sender out of the receiver's part, by a reclaim or
+    /// an agreed settlement
+    pub refunded: i128,
+    pub funded_amount: i128,
     pub is_cancelled: bool,
     pub sender: Address,
     pub receiver: Address,
@@ -47,25 +235,216 @@ pub struct Lockup {
     pub end_date: u64,
     pub rate: Rate,
     pub is_vesting: bool,
+    pub external_ref: ExternalRef,
+    /// The `(date, amount)` unlock schedule of tranched vestings, empty otherwise
+    pub tranches: Vec<(u64, i128)>,
+    pub withdraw_unlock_date: u64,
+    pub created_at: u64,
+    pub position: Position,
+    pub cliff_mode: CliffMode,
+    pub condition: Condition,
+    pub align_to_epoch: bool,
+    pub emit_events: bool,
+    pub receiver_transferable: bool,
+    pub use_ledger_seq: bool,
+    pub mode: StreamMode,
+}
+
+/// A lockup tagged with the version of the layout it's stored in, entries stored
+/// before versioning are untagged `LockupV1` structs. When a field is added, the
+/// current struct is kept as the previous layout and a variant is added for the
+/// new one, so older entries keep decoding
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum StoredLockup {
+    V2(Lockup),
 }
 
-impl Into<Lockup> for LockupInput {
-    fn into(self) -> Lockup {
+/// The layout lockups were stored with before the funding, tranches, position and
+/// the other options were added, entries in it are plain untagged structs. They
+/// are upgraded on read
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LockupV1 {
+    pub withdrawn: i128,
+    pub is_cancelled: bool,
+    pub sender: Address,
+    pub receiver: Address,
+    pub token: Address,
+    pub amount: i128,
+    pub cancellable_date: u64,
+    pub cancelled_date: u64,
+    pub cliff_date: u64,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub rate: Rate,
+    pub is_vesting: bool,
+}
+
+impl From<LockupV1> for Lockup {
+    fn from(lockup: LockupV1) -> Self {
+        let tranches = Vec::new(lockup.sender.env());
+
+        Lockup {
+            withdrawn: lockup.withdrawn,
+            refunded: 0,
+            // Lockups were always fully funded on creation before partial deposits
+            funded_amount: lockup.amount,
+            is_cancelled: lockup.is_cancelled,
+            sender: lockup.sender,
+            receiver: lockup.receiver,
+            token: lockup.token,
+            amount: lockup.amount,
+            cancellable_date: lockup.cancellable_date,
+            cancelled_date: lockup.cancelled_date,
+            cliff_date: lockup.cliff_date,
+            start_date: lockup.start_date,
+            end_date: lockup.end_date,
+            rate: lockup.rate,
+            is_vesting: lockup.is_vesting,
+            external_ref: ExternalRef::None,
+            tranches,
+            withdraw_unlock_date: 0,
+            created_at: 0,
+            position: Position::None,
+            cliff_mode: CliffMode::GateFromStart,
+            condition: Condition::None,
+            align_to_epoch: false,
+            emit_events: true,
+            receiver_transferable: true,
+            use_ledger_seq: false,
+            mode: StreamMode::Forward,
+        }
+    }
+}
+
+/// The core fields of a lockup as a flat tuple for clients which can't decode the
+/// full struct: sender, receiver, token, amount, withdrawn, start date, end date,
+/// cliff date, cancellable date, is cancelled and is vesting
+pub type FlatLockup = (
+    Address,
+    Address,
+    Address,
+    i128,
+    i128,
+    u64,
+    u64,
+    u64,
+    u64,
+    bool,
+    bool,
+);
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LockupDetail {
+    pub lockup: Lockup,
+    pub status: LockupStatus,
+    pub vested: i128,
+    pub withdrawable: i128,
+    pub progress_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CancellationResult {
+    pub id: u64,
+    pub cancelled: bool,
+    pub sender_amount: i128,
+    pub receiver_amount: i128,
+}
+
+impl From<Lockup> for FlatLockup {
+    fn from(lockup: Lockup) -> Self {
+        (
+            lockup.sender,
+            lockup.receiver,
+            lockup.token,
+            lockup.amount,
+            lockup.withdrawn,
+            lockup.start_date,
+            lockup.end_date,
+            lockup.cliff_date,
+            lockup.cancellable_date,
+            lockup.is_cancelled,
+            lockup.is_vesting,
+        )
+    }
+}
+
+/// Rebuilds the input a lockup was created with. The amount is net of a skimmed
+/// creation fee, and options which leave no trace on the lockup are defaulted
+impl From<Lockup> for LockupInput {
+    fn from(lockup: Lockup) -> Self {
+        LockupInput {
+            sender: lockup.sender,
+            receiver: lockup.receiver,
+            token: lockup.token,
+            amount: lockup.amount,
+            cancellable_date: lockup.cancellable_date,
+            cliff_date: lockup.cliff_date,
+            start_date: lockup.start_date,
+            end_date: lockup.end_date,
+            rate: lockup.rate,
+            options: LockupOptions {
+                external_ref: lockup.external_ref,
+                withdraw_unlock_date: match lockup.withdraw_unlock_date {
+                    0 => None,
+                    date => Some(date),
+                },
+                tokenized: lockup.position != Position::None,
+                cliff_mode: lockup.cliff_mode,
+                condition: lockup.condition,
+                align_to_epoch: lockup.align_to_epoch,
+                emit_events: match lockup.emit_events {
+                    true => None,
+                    false => Some(false),
+                },
+                receiver_transferable: match lockup.receiver_transferable {
+                    true => None,
+                    false => Some(false),
+                },
+                use_ledger_seq: lockup.use_ledger_seq,
+                mode: lockup.mode,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<LockupInput> for Lockup {
+    fn from(params: LockupInput) -> Self {
+        let tranches = Vec::new(params.sender.env());
+
         Lockup {
             withdrawn: 0,
+            refunded: 0,
+            funded_amount: params.options.initial_deposit.unwrap_or(params.amount),
             is_cancelled: false,
-            sender: self.sender.clone(),
-            receiver: self.receiver.clone(),
-            token: self.token.clone(),
-            amount: self.amount,
-            cancellable_date: self.cancellable_date,
+            sender: params.sender,
+            receiver: params.receiver,
+            token: params.token,
+            amount: params.amount,
+            cancellable_date: params.cancellable_date,
             cancelled_date: 0,
-            cliff_date: self.cliff_date,
-            start_date: self.start_date,
-            end_date: self.end_date,
+            cliff_date: params.cliff_date,
+            start_date: params.start_date,
+            end_date: params.end_date,
             // rate: Rate::Daily,
-            rate: self.rate.clone(),
+            rate: params.rate,
             is_vesting: false,
+            external_ref: params.options.external_ref,
+            tranches,
+            withdraw_unlock_date: params.options.withdraw_unlock_date.unwrap_or(0),
+            created_at: 0,
+            position: Position::None,
+            cliff_mode: params.options.cliff_mode,
+            condition: params.options.condition,
+            align_to_epoch: params.options.align_to_epoch,
+            emit_events: params.options.emit_events.unwrap_or(true),
+            receiver_transferable: params.options.receiver_transferable.unwrap_or(true),
+            use_ledger_seq: params.options.use_ledger_seq,
+            mode: params.options.mode,
         }
     }
 }
@@ -1,84 +1,442 @@
-use core::fmt::write;
+use soroban_sdk::{Address, Vec};
 
-use super::types::{Amounts, Lockup, Rate};
+use super::errors::CustomErrors;
+use super::types::{Amounts, CliffMode, Lockup, LockupStatus, Rate, StreamMode};
 
 pub fn calculate_stream_amounts(
     start_date: u64,
     end_date: u64,
     cliff_date: u64,
+    cliff_mode: CliffMode,
     current_date: u64,
     amount: i128,
-) -> Amounts {
+) -> Result<Amounts, CustomErrors> {
+    if end_date <= start_date {
+        return Err(CustomErrors::InvalidDuration);
+    }
+
     if current_date <= start_date {
-        return Amounts {
+        return Ok(Amounts {
             sender_amount: amount,
             receiver_amount: 0,
-        };
+        });
     }
 
     if current_date <= cliff_date {
-        return Amounts {
+        return Ok(Amounts {
             sender_amount: amount,
             receiver_amount: 0,
-        };
+        });
     }
 
     if current_date >= end_date {
-        return Amounts {
+        return Ok(Amounts {
             sender_amount: 0,
             receiver_amount: amount,
-        };
+        });
     }
 
-    let total_date: i128 = (end_date - start_date).into();
-    let proceeded_date: i128 = (current_date - start_date).into();
+    // Past the cliff, the amount either accrues since the start or only since the cliff
+    let accrual_start_date = match cliff_mode {
+        CliffMode::GateFromStart => start_date,
+        CliffMode::LinearFromCliff => start_date.max(cliff_date),
+    };
 
+    let total_date: i128 = (end_date - accrual_start_date).into();
+    let proceeded_date: i128 = (current_date - accrual_start_date).into();
+
+    // Multiplying before dividing keeps the precision for small amounts streamed
+    // over long durations, where a per-second rate would round down to zero
     let receiver_amount = amount * proceeded_date / total_date;
     let sender_amount = amount - receiver_amount;
 
-    Amounts {
+    Ok(Amounts {
         sender_amount,
         receiver_amount,
-    }
+    })
 }
 
+/// Unlocks the amount in steps of `rate` seconds. Steps are counted from the start
+/// date, or with `align_to_epoch` from the multiples of `rate` since timestamp 0,
+/// in which case the first step only unlocks the part of a period after the start
 pub fn calculate_vesting_amounts(
     start_date: u64,
     end_date: u64,
     cliff_date: u64,
     current_date: u64,
     rate: Rate,
+    align_to_epoch: bool,
     amount: i128,
-) -> Amounts {
+) -> Result<Amounts, CustomErrors> {
+    if end_date <= start_date {
+        return Err(CustomErrors::InvalidDuration);
+    }
+
     if current_date <= start_date || current_date <= cliff_date {
-        return Amounts {
+        return Ok(Amounts {
             sender_amount: amount,
             receiver_amount: 0,
-        };
+        });
     }
 
     if current_date >= end_date {
-        return Amounts {
+        return Ok(Amounts {
             sender_amount: 0,
             receiver_amount: amount,
-        };
+        });
     }
 
     let total_date: i128 = (end_date - start_date).into();
-    let proceeded_date: i128 = (current_date - start_date).into();
-    let rate_in_seconds = rate as i128;
+    let rate_in_seconds = rate as u64;
 
-    let times = proceeded_date / rate_in_seconds;
-    let one_time_amount = amount * rate_in_seconds / total_date;
+    // The date of the latest step reached, e.g. with a daily rate and a start date at
+    // noon, the steps are at noon from the start and at midnight when aligned
+    let stepped_date = if align_to_epoch {
+        current_date / rate_in_seconds * rate_in_seconds
+    } else {
+        start_date + (current_date - start_date) / rate_in_seconds * rate_in_seconds
+    };
 
+    let stepped_duration: i128 = stepped_date.saturating_sub(start_date).into();
+
+    // Multiplying before dividing keeps the precision for small amounts spread over
+    // many periods, where a per-period amount would round down to zero
     // TODO: if duration / rate is not dividable, what happens? check all of them
-    let receiver_amount = times * one_time_amount;
+    let receiver_amount = amount * stepped_duration / total_date;
     let sender_amount = amount - receiver_amount;
 
-    Amounts {
+    Ok(Amounts {
         sender_amount,
         receiver_amount,
+    })
+}
+
+/// Unlocks each tranche in full once its date is reached
+pub fn calculate_tranche_amounts(
+    tranches: &Vec<(u64, i128)>,
+    current_date: u64,
+    amount: i128,
+) -> Amounts {
+    let mut receiver_amount = 0;
+
+    for (date, tranche_amount) in tranches.iter() {
+        if date > current_date {
+            break;
+        }
+
+        receiver_amount += tranche_amount;
+    }
+
+    Amounts {
+        sender_amount: amount - receiver_amount,
+        receiver_amount,
+    }
+}
+
+/// Tranches must be sorted by date, fall within `(start_date, end_date]`, have
+/// positive amounts and sum up to the lockup amount
+pub fn validate_tranches(
+    tranches: &Vec<(u64, i128)>,
+    start_date: u64,
+    end_date: u64,
+    amount: i128,
+) -> Result<(), CustomErrors> {
+    if tranches.is_empty() {
+        return Err(CustomErrors::InvalidTranches);
+    }
+
+    let mut previous_date = start_date;
+    let mut total: i128 = 0;
+
+    for (date, tranche_amount) in tranches.iter() {
+        if date <= previous_date || date > end_date || tranche_amount <= 0 {
+            return Err(CustomErrors::InvalidTranches);
+        }
+
+        previous_date = date;
+        total = total
+            .checked_add(tranche_amount)
+            .ok_or(CustomErrors::InvalidTranches)?;
+    }
+
+    if total != amount {
+        return Err(CustomErrors::InvalidTranches);
+    }
+
+    Ok(())
+}
+
+/// Returns the amount a vesting unlocks per whole period, a remainder shorter than a
+/// period is unlocked at the end date and a duration shorter than one counts as one
+pub fn calculate_period_amount(amount: i128, start_date: u64, end_date: u64, rate: Rate) -> i128 {
+    let periods = ((end_date - start_date) / rate as u64).max(1);
+
+    amount / periods as i128
+}
+
+/// Returns the fee in basis points of the amount, amounts close to `i128::MAX`
+/// overflow before being divided
+pub fn calculate_fee(amount: i128, fee_bps: u32) -> Result<i128, CustomErrors> {
+    amount
+        .checked_mul(fee_bps as i128)
+        .map(|fee| fee / 10000)
+        .ok_or(CustomErrors::AmountOverflows)
+}
+
+pub fn calculate_lockup_amounts(
+    lockup: &Lockup,
+    current_date: u64,
+) -> Result<Amounts, CustomErrors> {
+    if !lockup.tranches.is_empty() {
+        return Ok(calculate_tranche_amounts(
+            &lockup.tranches,
+            current_date,
+            lockup.amount,
+        ));
+    }
+
+    if lockup.is_vesting {
+        return calculate_vesting_amounts(
+            lockup.start_date,
+            lockup.end_date,
+            lockup.cliff_date,
+            current_date,
+            lockup.rate,
+            lockup.align_to_epoch,
+            lockup.amount,
+        );
+    }
+
+    let amounts = calculate_stream_amounts(
+        lockup.start_date,
+        lockup.end_date,
+        lockup.cliff_date,
+        lockup.cliff_mode,
+        current_date,
+        lockup.amount,
+    )?;
+
+    match lockup.mode {
+        StreamMode::Forward => Ok(amounts),
+        // The receiver's part shrinks over time, but never below what they claimed
+        StreamMode::Reverse => {
+            let receiver_amount = amounts.sender_amount.max(paid_out(lockup));
+
+            Ok(Amounts {
+                sender_amount: lockup.amount - receiver_amount,
+                receiver_amount,
+            })
+        }
+    }
+}
+
+pub fn check_cancellable(lockup: &Lockup, current_date: u64) -> Result<(), CustomErrors> {
+    if lockup.is_cancelled {
+        return Err(CustomErrors::LockupAlreadyCanceled);
+    }
+
+    // Reversed streams end with the sender's part in the lockup, so the sender can
+    // still cancel them to take it back
+    if lockup.end_date <= current_date && lockup.mode == StreamMode::Forward {
+        return Err(CustomErrors::LockupAlreadySettled);
+    }
+
+    if lockup.cancellable_date > current_date {
+        return Err(CustomErrors::LockupNotCancellableYet);
+    }
+
+    Ok(())
+}
+
+/// Returns the date the lockup amounts should be calculated at, which is the
+/// cancellation date for cancelled lockups since nothing accrues after it
+pub fn effective_date(lockup: &Lockup, current_date: u64) -> u64 {
+    if lockup.is_cancelled {
+        return lockup.cancelled_date;
+    }
+
+    current_date
+}
+
+pub fn calculate_vested(lockup: &Lockup, current_date: u64) -> Result<i128, CustomErrors> {
+    let date = effective_date(lockup, current_date);
+
+    let amounts = calculate_lockup_amounts(lockup, date)?;
+
+    Ok(amounts.receiver_amount)
+}
+
+pub fn calculate_progress_bps(lockup: &Lockup, current_date: u64) -> u32 {
+    let date = effective_date(lockup, current_date);
+
+    if date <= lockup.start_date || lockup.end_date <= lockup.start_date {
+        return 0;
+    }
+
+    if date >= lockup.end_date {
+        return 10000;
+    }
+
+    ((date - lockup.start_date) * 10000 / (lockup.end_date - lockup.start_date)) as u32
+}
+
+pub fn get_lockup_status(lockup: &Lockup, current_date: u64) -> LockupStatus {
+    if lockup.is_cancelled {
+        return LockupStatus::Cancelled;
+    }
+
+    if current_date <= lockup.start_date {
+        return LockupStatus::NotStarted;
+    }
+
+    if current_date >= lockup.end_date && paid_out(lockup) >= lockup.amount {
+        return LockupStatus::Settled;
+    }
+
+    if current_date >= lockup.end_date {
+        return LockupStatus::Ended;
+    }
+
+    LockupStatus::Active
+}
+
+pub fn calculate_withdrawable(lockup: &Lockup, current_date: u64) -> Result<i128, CustomErrors> {
+    let date = effective_date(lockup, current_date);
+
+    if date <= lockup.cliff_date {
+        return Ok(0);
+    }
+
+    let amounts = calculate_lockup_amounts(lockup, date)?;
+
+    Ok((amounts.receiver_amount.min(lockup.funded_amount) - paid_out(lockup)).max(0))
+}
+
+/// Returns the part of `total` a member of a group is entitled to by their shares,
+/// `None` if they aren't a member. The rounding remainder goes to the last member
+pub fn calculate_group_share(
+    total: i128,
+    shares: &Vec<(Address, u32)>,
+    member: &Address,
+) -> Result<Option<i128>, CustomErrors> {
+    let total_shares: i128 = shares.iter().map(|(_, shares)| shares as i128).sum();
+    let last_index = shares.len().saturating_sub(1) as usize;
+
+    let mut others = 0;
+    let mut own = None;
+
+    for (index, (address, shares)) in shares.iter().enumerate() {
+        let amount = total
+            .checked_mul(shares as i128)
+            .ok_or(CustomErrors::AmountOverflows)?
+            / total_shares;
+
+        if address == *member {
+            own = Some((index, amount));
+        }
+
+        if index < last_index {
+            others += amount;
+        }
+    }
+
+    Ok(match own {
+        Some((index, _)) if index == last_index => Some(total - others),
+        Some((_, amount)) => Some(amount),
+        None => None,
+    })
+}
+
+/// Returns what was paid out of the receiver's part, to the receiver or back to
+/// the sender
+pub fn paid_out(lockup: &Lockup) -> i128 {
+    lockup.withdrawn + lockup.refunded
+}
+
+/// A lockup is settled when nothing is left to be withdrawn from it, either
+/// because it's cancelled and drained or because it's ended and fully withdrawn
+pub fn is_settled(lockup: &Lockup, current_date: u64) -> bool {
+    if lockup.is_cancelled {
+        return calculate_withdrawable(lockup, current_date) == Ok(0);
     }
+
+    current_date >= lockup.end_date && paid_out(lockup) >= lockup.amount
+}
+
+/// Returns the amount unlocked per unit of the lockup's clock, a second or a ledger
+/// for lockups following the ledger sequence. It's averaged over the whole duration
+/// for vestings since they unlock in steps. Streams accruing from the cliff spread the
+/// amount from the cliff on, and the rate is negative for reversed streams since
+/// the receiver's part shrinks
+pub fn calculate_rate_per_unit(lockup: &Lockup) -> Result<i128, CustomErrors> {
+    if lockup.end_date <= lockup.start_date {
+        return Err(CustomErrors::InvalidDuration);
+    }
+
+    if lockup.is_vesting || !lockup.tranches.is_empty() {
+        let duration: i128 = (lockup.end_date - lockup.start_date).into();
+
+        return Ok(lockup.amount / duration);
+    }
+
+    // Same accrual window as `calculate_stream_amounts`
+    let accrual_start_date = match lockup.cliff_mode {
+        CliffMode::GateFromStart => lockup.start_date,
+        CliffMode::LinearFromCliff => lockup.start_date.max(lockup.cliff_date),
+    };
+
+    let duration: i128 = (lockup.end_date - accrual_start_date).into();
+    let rate = lockup.amount / duration;
+
+    match lockup.mode {
+        StreamMode::Forward => Ok(rate),
+        StreamMode::Reverse => Ok(-rate),
+    }
+}
+
+/// Returns the next date something unlocks and the amount unlocking then. Vestings
+/// unlock at period boundaries or tranche dates, while streams unlock continuously
+/// so the current date and the per-unit rate are returned. The amount is 0 when
+/// nothing is left to unlock, which is always the case for reversed streams
+pub fn calculate_next_unlock(
+    lockup: &Lockup,
+    current_date: u64,
+) -> Result<(u64, i128), CustomErrors> {
+    if lockup.is_cancelled || current_date >= lockup.end_date || lockup.mode == StreamMode::Reverse
+    {
+        return Ok((current_date, 0));
+    }
+
+    if !lockup.is_vesting {
+        return Ok((current_date, calculate_rate_per_unit(lockup)?));
+    }
+
+    let next_date = if lockup.tranches.is_empty() {
+        let rate_in_seconds = lockup.rate as u64;
+        let from_date = current_date.max(lockup.cliff_date).max(lockup.start_date);
+
+        let next_step = if lockup.align_to_epoch {
+            (from_date / rate_in_seconds + 1) * rate_in_seconds
+        } else {
+            let periods = (from_date - lockup.start_date) / rate_in_seconds + 1;
+
+            lockup.start_date + periods * rate_in_seconds
+        };
+
+        next_step.min(lockup.end_date)
+    } else {
+        lockup
+            .tranches
+            .iter()
+            .map(|(date, _)| date)
+            .find(|date| *date > current_date)
+            .unwrap_or(lockup.end_date)
+    };
+
+    let vested_now = calculate_lockup_amounts(lockup, current_date)?.receiver_amount;
+    let vested_next = calculate_lockup_amounts(lockup, next_date)?.receiver_amount;
+
+    Ok((next_date, vested_next - vested_now))
 }
 
 pub fn calculate_additional_time(lockup: &Lockup, adding_amount: i128) -> u64 {
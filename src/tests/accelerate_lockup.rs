@@ -0,0 +1,86 @@
+use soroban_sdk::vec;
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{LockupInput, Rate},
+};
+
+use super::setup::{SetupStreamTest, StreamFields};
+
+#[test]
+fn test_accelerate_lockup_should_increase_the_withdrawable() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(40);
+    assert_eq!(vars.contract.get_withdrawable(&id), 400);
+
+    vars.contract.accelerate_lockup(&id, &50);
+
+    let lockup = vars.contract.get_lockup(&id);
+
+    assert_eq!(lockup.end_date, 50);
+    assert_eq!(lockup.amount, 1000);
+    assert_eq!(vars.contract.get_withdrawable(&id), 800);
+
+    vars.move_ledger_timestamp_to(50);
+    assert_eq!(vars.contract.get_withdrawable(&id), 1000);
+}
+
+#[test]
+fn test_accelerate_lockup_should_vest_everything_when_ending_now() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(40);
+    vars.contract.accelerate_lockup(&id, &40);
+
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 1000);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+}
+
+#[test]
+fn test_accelerate_lockup_should_pull_in_later_tranches() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = LockupInput {
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 400,
+        rate: Rate::Daily,
+        ..vars.lockup_input()
+    };
+    let tranches = vec![&vars.env, (100, 250), (200, 250), (300, 250), (400, 250)];
+
+    let id = vars.contract.create_tranched_vesting(&params, &tranches);
+
+    vars.move_ledger_timestamp_to(150);
+    vars.contract.accelerate_lockup(&id, &200);
+
+    assert_eq!(
+        vars.contract.get_lockup(&id).tranches,
+        vec![&vars.env, (100, 250), (200, 250), (200, 250), (200, 250)]
+    );
+
+    vars.move_ledger_timestamp_to(200);
+    assert_eq!(vars.contract.get_withdrawable(&id), 1000);
+}
+
+#[test]
+fn test_accelerate_lockup_should_revert_when_new_end_date_is_in_the_past() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(40);
+
+    let result = vars.contract.try_accelerate_lockup(&id, &39);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidEndDate)));
+}
+
+#[test]
+fn test_accelerate_lockup_should_revert_when_new_end_date_is_after_end_date() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let result = vars.contract.try_accelerate_lockup(&id, &101);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidEndDate)));
+}
@@ -0,0 +1,64 @@
+use soroban_sdk::{testutils::Ledger, vec};
+
+use crate::base::errors::CustomErrors;
+
+use super::setup::{SetupStreamTest, StreamFields};
+
+const DAY_IN_LEDGERS: u32 = 17280;
+
+#[test]
+fn test_bump_lockups_ttl_should_bump_present_lockups_and_skip_missing_ones() {
+    let (vars, id0) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        amount: 500,
+        ..Default::default()
+    });
+
+    vars.env
+        .ledger()
+        .with_mut(|ledger| ledger.sequence_number += 55 * DAY_IN_LEDGERS);
+
+    let bumped = vars.contract.bump_lockups_ttl(&vec![&vars.env, id0, 99]);
+
+    assert_eq!(bumped, 1);
+
+    vars.env
+        .ledger()
+        .with_mut(|ledger| ledger.sequence_number += 30 * DAY_IN_LEDGERS);
+
+    assert_eq!(vars.contract.get_lockup(&id0).amount, 500);
+}
+
+#[test]
+fn test_bump_lockups_ttl_should_revert_when_batch_is_too_large() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let mut ids = vec![&vars.env];
+
+    for id in 0..21 {
+        ids.push_back(id);
+    }
+
+    let result = vars.contract.try_bump_lockups_ttl(&ids);
+
+    assert_eq!(result, Err(Ok(CustomErrors::BatchTooLarge)));
+}
+
+#[test]
+fn test_get_lockup_storage_info_should_return_size_and_bump_target() {
+    let (vars, id0) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let (size, bump_target) = vars.contract.get_lockup_storage_info(&id0);
+
+    assert!(size > 0);
+    assert_eq!(size, vars.contract.export_lockup(&id0).len());
+    assert_eq!(bump_target, 60 * DAY_IN_LEDGERS);
+}
+
+#[test]
+fn test_get_lockup_storage_info_should_revert_when_lockup_does_not_exist() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let result = vars.contract.try_get_lockup_storage_info(&7);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupNotFound)));
+}
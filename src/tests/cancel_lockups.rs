@@ -0,0 +1,134 @@
+use soroban_sdk::{testutils::Address as _, vec, Address, Vec};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{CancellationResult, LockupInput},
+};
+
+use super::setup::SetupStreamTest;
+
+fn create_streams(vars: &SetupStreamTest) -> (u64, u64, u64) {
+    let now = vars.env.ledger().timestamp();
+
+    let params = |cancellable_date: u64| LockupInput {
+        cancellable_date,
+        ..vars.lockup_input()
+    };
+
+    let id0 = vars.contract.create_stream(&params(now));
+    let id1 = vars.contract.create_stream(&params(now + 80));
+    let id2 = vars.contract.create_stream(&params(now));
+
+    (id0, id1, id2)
+}
+
+#[test]
+fn test_cancel_lockups_should_skip_ineligible_lockups_when_not_strict() {
+    let vars = SetupStreamTest::setup(3000);
+
+    let (id0, id1, id2) = create_streams(&vars);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let results =
+        vars.contract
+            .cancel_lockups(&vars.admin, &vec![&vars.env, id0, id1, id2, 10], &false);
+
+    assert_eq!(
+        results,
+        vec![
+            &vars.env,
+            CancellationResult {
+                id: id0,
+                cancelled: true,
+                sender_amount: 500,
+                receiver_amount: 500,
+            },
+            CancellationResult {
+                id: id1,
+                cancelled: false,
+                sender_amount: 0,
+                receiver_amount: 0,
+            },
+            CancellationResult {
+                id: id2,
+                cancelled: true,
+                sender_amount: 500,
+                receiver_amount: 500,
+            },
+            CancellationResult {
+                id: 10,
+                cancelled: false,
+                sender_amount: 0,
+                receiver_amount: 0,
+            },
+        ]
+    );
+    assert!(vars.contract.get_lockup(&id0).is_cancelled);
+    assert!(!vars.contract.get_lockup(&id1).is_cancelled);
+    assert!(vars.contract.get_lockup(&id2).is_cancelled);
+    assert_eq!(vars.token.balance(&vars.contract.address), 1000);
+}
+
+#[test]
+fn test_cancel_lockups_should_revert_on_ineligible_lockups_when_strict() {
+    let vars = SetupStreamTest::setup(3000);
+
+    let (id0, id1, id2) = create_streams(&vars);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let result =
+        vars.contract
+            .try_cancel_lockups(&vars.admin, &vec![&vars.env, id0, id1, id2], &true);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupNotCancellableYet)));
+    assert!(!vars.contract.get_lockup(&id0).is_cancelled);
+    assert_eq!(vars.token.balance(&vars.contract.address), 3000);
+}
+
+#[test]
+fn test_cancel_lockups_should_cancel_everything_when_strict_and_eligible() {
+    let vars = SetupStreamTest::setup(3000);
+
+    let (id0, _, id2) = create_streams(&vars);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let results = vars
+        .contract
+        .cancel_lockups(&vars.admin, &vec![&vars.env, id0, id2], &true);
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|result| result.cancelled));
+}
+
+#[test]
+fn test_cancel_lockups_should_not_cancel_lockups_of_other_senders() {
+    let vars = SetupStreamTest::setup(3000);
+
+    let (id0, _, _) = create_streams(&vars);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let other = Address::generate(&vars.env);
+    let result = vars
+        .contract
+        .try_cancel_lockups(&other, &vec![&vars.env, id0], &true);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupSenderMismatch)));
+}
+
+#[test]
+fn test_cancel_lockups_should_revert_when_batch_is_too_large() {
+    let vars = SetupStreamTest::setup(3000);
+
+    let mut ids = Vec::new(&vars.env);
+    for id in 0..21 {
+        ids.push_back(id);
+    }
+
+    let result = vars.contract.try_cancel_lockups(&vars.admin, &ids, &false);
+
+    assert_eq!(result, Err(Ok(CustomErrors::BatchTooLarge)));
+}
@@ -1,7 +1,7 @@
 use soroban_sdk::{
     symbol_short,
-    testutils::{Events, Ledger},
-    IntoVal,
+    testutils::{Address as _, Events, Ledger},
+    Address, IntoVal,
 };
 
 use crate::{
@@ -13,7 +13,7 @@ use crate::{
 fn test_stream_should_be_cancelled_after_creation() {
     let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
 
-    let amounts = vars.contract.cancel_lockup(&id);
+    let amounts = vars.contract.cancel_lockup(&id, &true);
     let stream = vars.contract.get_lockup(&id);
 
     assert_eq!(vars.token.balance(&vars.contract.address), 0);
@@ -28,13 +28,18 @@ fn test_stream_should_be_cancelled_after_creation() {
 fn test_cancel_stream_should_emit_event() {
     let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
 
-    vars.contract.cancel_lockup(&id);
+    vars.contract.cancel_lockup(&id, &true);
 
     let events = vars.env.events().all();
     assert!(events.contains((
         vars.contract.address.clone(),
-        (symbol_short!("LOCKUP"), symbol_short!("CANCELLED")).into_val(&vars.env),
-        id.into_val(&vars.env)
+        (
+            symbol_short!("LOCKUP"),
+            symbol_short!("CANCELLED"),
+            vars.admin.clone()
+        )
+            .into_val(&vars.env),
+        (id, 1u64).into_val(&vars.env)
     )))
 }
 
@@ -46,7 +51,7 @@ fn test_cancel_stream_should_transfer_tokens_to_both_sides() {
     vars.move_ledger_timestamp_to(new_timestamp);
     assert_eq!(vars.env.ledger().get().timestamp, new_timestamp);
 
-    let amounts = vars.contract.cancel_lockup(&id);
+    let amounts = vars.contract.cancel_lockup(&id, &true);
     let stream = vars.contract.get_lockup(&id);
 
     assert_eq!(vars.token.balance(&vars.contract.address), 0);
@@ -63,8 +68,8 @@ fn test_cancel_stream_should_transfer_tokens_to_both_sides() {
 fn test_cancel_stream_should_revert_when_stream_is_already_cancelled() {
     let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
 
-    vars.contract.cancel_lockup(&id);
-    let result = vars.contract.try_cancel_lockup(&id);
+    vars.contract.cancel_lockup(&id, &true);
+    let result = vars.contract.try_cancel_lockup(&id, &true);
 
     assert_eq!(result, Err(Ok(errors::CustomErrors::LockupAlreadyCanceled)));
 }
@@ -77,7 +82,7 @@ fn test_cancel_stream_should_revert_when_stream_is_already_settled() {
     vars.move_ledger_timestamp_to(new_timestamp);
     assert_eq!(vars.env.ledger().get().timestamp, new_timestamp);
 
-    let result = vars.contract.try_cancel_lockup(&id);
+    let result = vars.contract.try_cancel_lockup(&id, &true);
 
     assert_eq!(result, Err(Ok(errors::CustomErrors::LockupAlreadySettled)));
 }
@@ -93,10 +98,346 @@ fn test_cancel_stream_should_revert_when_cancellable_date_is_not_reached() {
     vars.move_ledger_timestamp_to(new_timestamp);
     assert_eq!(vars.env.ledger().get().timestamp, new_timestamp);
 
-    let result = vars.contract.try_cancel_lockup(&id);
+    let result = vars.contract.try_cancel_lockup(&id, &true);
 
     assert_eq!(
         result,
         Err(Ok(errors::CustomErrors::LockupNotCancellableYet))
     );
 }
+
+#[test]
+fn test_cancel_stream_should_never_transfer_more_than_the_remaining_balance() {
+    for timestamp in 1..97 {
+        let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+            amount: 1001,
+            end_date: 97,
+            ..Default::default()
+        });
+
+        vars.move_ledger_timestamp_to(timestamp / 2);
+        let withdrawn = if timestamp > 1 {
+            vars.contract.withdraw_lockup(&id, &0)
+        } else {
+            0
+        };
+
+        vars.move_ledger_timestamp_to(timestamp);
+        let (sender_amount, receiver_amount) = vars.contract.cancel_lockup(&id, &true);
+        let stream = vars.contract.get_lockup(&id);
+
+        assert!(sender_amount >= 0);
+        assert!(receiver_amount >= 0);
+        assert_eq!(sender_amount + receiver_amount, 1001 - withdrawn);
+        assert_eq!(sender_amount, 1001 - stream.withdrawn);
+        assert_eq!(vars.token.balance(&vars.contract.address), 0);
+    }
+}
+
+#[test]
+fn test_cancel_stream_should_revert_when_withdrawn_exceeds_vested() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let mut stream = vars.contract.get_lockup(&id);
+    stream.withdrawn = 800;
+    vars.set_lockup(id, &stream);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let result = vars.contract.try_cancel_lockup(&id, &true);
+
+    assert_eq!(
+        result,
+        Err(Ok(errors::CustomErrors::WithdrawnExceedsVested))
+    );
+    assert_eq!(vars.contract.get_withdrawable(&id), 0);
+    assert_eq!(vars.token.balance(&vars.contract.address), vars.amount);
+    assert!(!vars.contract.get_lockup(&id).is_cancelled);
+}
+
+#[test]
+fn test_cancel_stream_should_keep_vested_amount_claimable_when_not_pushed() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+
+    let amounts = vars.contract.cancel_lockup(&id, &false);
+    let stream = vars.contract.get_lockup(&id);
+
+    assert_eq!(amounts, (500, 0));
+    assert_eq!(vars.token.balance(&vars.admin), 500);
+    assert_eq!(vars.token.balance(&stream.receiver), 0);
+    assert_eq!(vars.token.balance(&vars.contract.address), 500);
+
+    vars.move_ledger_timestamp_to(80);
+
+    let withdrawn = vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(withdrawn, 500);
+    assert_eq!(vars.token.balance(&stream.receiver), 500);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::LockupIsCanceled)));
+}
+
+#[test]
+fn test_withdraw_should_revert_after_cancel_when_pushed_to_receiver() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+
+    let amounts = vars.contract.cancel_lockup(&id, &true);
+    let stream = vars.contract.get_lockup(&id);
+
+    assert_eq!(amounts, (500, 500));
+    assert_eq!(vars.token.balance(&stream.receiver), 500);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::LockupIsCanceled)));
+}
+
+#[test]
+fn test_can_cancel_should_be_true_for_the_sender_of_a_cancellable_stream() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+
+    assert!(vars.contract.can_cancel(&id, &vars.admin));
+}
+
+#[test]
+fn test_can_cancel_should_be_false_for_others() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let stream = vars.contract.get_lockup(&id);
+
+    vars.move_ledger_timestamp_to(50);
+
+    assert!(!vars.contract.can_cancel(&id, &stream.receiver));
+    assert!(!vars.contract.can_cancel(&id, &Address::generate(&vars.env)));
+}
+
+#[test]
+fn test_can_cancel_should_be_false_before_the_cancellable_date() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        cancellable_date: 50,
+        ..Default::default()
+    });
+
+    vars.move_ledger_timestamp_to(49);
+    assert!(!vars.contract.can_cancel(&id, &vars.admin));
+
+    vars.move_ledger_timestamp_to(50);
+    assert!(vars.contract.can_cancel(&id, &vars.admin));
+}
+
+#[test]
+fn test_can_cancel_should_be_false_when_cancelled_or_settled() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(100);
+    assert!(!vars.contract.can_cancel(&id, &vars.admin));
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.cancel_lockup(&id, &true);
+    assert!(!vars.contract.can_cancel(&id, &vars.admin));
+}
+
+#[test]
+fn test_can_cancel_should_be_false_when_stream_does_not_exist() {
+    let (vars, _) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    assert!(!vars.contract.can_cancel(&2, &vars.admin));
+}
+
+#[test]
+fn test_get_refundable_to_sender_should_follow_the_cancellable_date() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        cancellable_date: 30,
+        ..Default::default()
+    });
+
+    vars.move_ledger_timestamp_to(29);
+    assert_eq!(vars.contract.get_refundable_to_sender(&id), 0);
+
+    vars.move_ledger_timestamp_to(30);
+    assert_eq!(vars.contract.get_refundable_to_sender(&id), 700);
+
+    vars.move_ledger_timestamp_to(40);
+    assert_eq!(vars.contract.get_refundable_to_sender(&id), 600);
+
+    let (sender_amount, _) = vars.contract.cancel_lockup(&id, &true);
+
+    assert_eq!(sender_amount, 600);
+    assert_eq!(vars.contract.get_refundable_to_sender(&id), 0);
+}
+
+#[test]
+fn test_get_time_since_cancel_should_return_the_elapsed_seconds() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(30);
+
+    let result = vars.contract.try_get_time_since_cancel(&id);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::LockupNotCancelled)));
+
+    vars.contract.cancel_lockup(&id, &true);
+
+    assert_eq!(vars.contract.get_time_since_cancel(&id), 0);
+
+    vars.move_ledger_timestamp_to(75);
+
+    assert_eq!(vars.contract.get_time_since_cancel(&id), 45);
+}
+
+#[test]
+fn test_settle_lockup_should_split_the_remaining_balance_as_agreed() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let stream = vars.contract.get_lockup(&id);
+
+    vars.env.ledger().with_mut(|l| l.timestamp = 20);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    let amounts = vars.contract.settle_lockup(&id, &500);
+
+    let authorizers: super::std::vec::Vec<Address> = vars
+        .env
+        .auths()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+
+    assert_eq!(
+        authorizers,
+        [stream.sender.clone(), stream.receiver.clone()]
+    );
+    assert_eq!(amounts, (300, 500));
+    assert_eq!(vars.token.balance(&stream.sender), 300);
+    assert_eq!(vars.token.balance(&stream.receiver), 700);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+    assert!(vars.contract.get_lockup(&id).is_cancelled);
+    assert!(vars.contract.is_lockup_settled(&id));
+    assert_eq!(vars.contract.get_withdrawable(&id), 0);
+    assert_eq!(vars.contract.get_lockup_withdrawn(&id), 700);
+    assert_eq!(vars.contract.get_lockup(&id).refunded, 300);
+}
+
+#[test]
+fn test_settle_lockup_should_revert_when_the_split_exceeds_the_remaining_balance() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.env.ledger().with_mut(|l| l.timestamp = 20);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    let result = vars.contract.try_settle_lockup(&id, &801);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::InvalidAmount)));
+
+    let result = vars.contract.try_settle_lockup(&id, &-1);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::InvalidAmount)));
+
+    vars.contract.cancel_lockup(&id, &true);
+
+    let result = vars.contract.try_settle_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::LockupAlreadyCanceled)));
+}
+
+#[test]
+fn test_renew_stream_should_only_move_the_difference_from_the_sender() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let stream = vars.contract.get_lockup(&id);
+
+    soroban_sdk::token::StellarAssetClient::new(&vars.env, &vars.token.address)
+        .mint(&vars.admin, &500);
+    vars.token
+        .approve(&vars.admin, &vars.contract.address, &1000, &6311000);
+
+    vars.env.ledger().with_mut(|l| l.timestamp = 40);
+
+    let new_receiver = Address::generate(&vars.env);
+    let new_params = crate::base::types::LockupInput {
+        receiver: new_receiver.clone(),
+        cancellable_date: 40,
+        cliff_date: 40,
+        start_date: 40,
+        end_date: 240,
+        ..vars.lockup_input()
+    };
+
+    let new_id = vars.contract.renew_stream(&id, &new_params);
+    let renewed = vars.contract.get_lockup(&new_id);
+
+    assert!(vars.contract.get_lockup(&id).is_cancelled);
+    assert_eq!(renewed.receiver, new_receiver);
+    assert_eq!(renewed.amount, 1000);
+    assert_eq!(vars.token.balance(&stream.receiver), 400);
+    assert_eq!(vars.token.balance(&vars.admin), 500 - (1000 - 600));
+    assert_eq!(vars.token.balance(&vars.contract.address), 1000);
+}
+
+#[test]
+fn test_renew_stream_should_revert_for_another_sender() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let stream = vars.contract.get_lockup(&id);
+
+    let new_params = crate::base::types::LockupInput {
+        sender: Address::generate(&vars.env),
+        receiver: stream.receiver,
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        ..vars.lockup_input()
+    };
+
+    let result = vars.contract.try_renew_stream(&id, &new_params);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::LockupSenderMismatch)));
+    assert!(!vars.contract.get_lockup(&id).is_cancelled);
+}
+
+fn create_stream_starting_now(vars: &SetupStreamTest) -> u64 {
+    vars.contract.create_stream(&vars.lockup_input())
+}
+
+#[test]
+fn test_cancel_stream_starting_now_should_refund_the_sender_fully() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.env.ledger().with_mut(|l| l.timestamp = 500);
+
+    let id = create_stream_starting_now(&vars);
+    let stream = vars.contract.get_lockup(&id);
+
+    assert!(vars.contract.can_cancel(&id, &vars.admin));
+    assert_eq!(vars.contract.cancel_lockup(&id, &true), (1000, 0));
+    assert_eq!(vars.token.balance(&vars.admin), 1000);
+    assert_eq!(vars.token.balance(&stream.receiver), 0);
+    assert!(vars.contract.is_lockup_settled(&id));
+}
+
+#[test]
+fn test_withdraw_stream_starting_now_should_have_nothing_until_time_passes() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.env.ledger().with_mut(|l| l.timestamp = 500);
+
+    let id = create_stream_starting_now(&vars);
+
+    // Nothing has elapsed yet, so the withdrawal reverts as not started
+    assert_eq!(vars.contract.get_withdrawable(&id), 0);
+    assert_eq!(
+        vars.contract.try_withdraw_lockup(&id, &0),
+        Err(Ok(errors::CustomErrors::LockupNotStartedYet))
+    );
+
+    vars.env.ledger().with_mut(|l| l.timestamp = 501);
+
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 10);
+}
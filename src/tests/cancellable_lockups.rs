@@ -0,0 +1,99 @@
+use soroban_sdk::{testutils::Address as _, vec, Address};
+
+use crate::base::types::LockupInput;
+
+use super::setup::SetupStreamTest;
+
+fn create_stream(vars: &SetupStreamTest, cancellable_date: u64) -> u64 {
+    let params = LockupInput {
+        amount: 100,
+        cancellable_date,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_stream(&params)
+}
+
+#[test]
+fn test_get_cancellable_lockups_should_skip_lockups_before_their_cancellable_date() {
+    let vars = SetupStreamTest::setup(400);
+
+    let id0 = create_stream(&vars, 10);
+    let id1 = create_stream(&vars, 50);
+    let id2 = create_stream(&vars, 20);
+    let id3 = create_stream(&vars, 0);
+
+    vars.move_ledger_timestamp_to(30);
+    vars.contract.cancel_lockup(&id3, &true);
+
+    assert_eq!(
+        vars.contract
+            .get_cancellable_lockups(&vars.admin, &0, &10)
+            .0,
+        vec![&vars.env, id0, id2]
+    );
+    assert_eq!(
+        vars.contract.get_cancellable_lockups(&vars.admin, &0, &1).0,
+        vec![&vars.env, id0]
+    );
+
+    vars.move_ledger_timestamp_to(60);
+
+    assert_eq!(
+        vars.contract
+            .get_cancellable_lockups(&vars.admin, &0, &10)
+            .0,
+        vec![&vars.env, id0, id1, id2]
+    );
+
+    vars.move_ledger_timestamp_to(100);
+
+    assert_eq!(
+        vars.contract
+            .get_cancellable_lockups(&vars.admin, &0, &10)
+            .0,
+        vec![&vars.env]
+    );
+}
+
+#[test]
+fn test_get_cancellable_lockups_should_return_nothing_for_other_senders() {
+    let vars = SetupStreamTest::setup(100);
+
+    create_stream(&vars, 0);
+
+    let other = Address::generate(&vars.env);
+
+    assert_eq!(
+        vars.contract.get_cancellable_lockups(&other, &0, &10).0,
+        vec![&vars.env]
+    );
+}
+
+#[test]
+fn test_get_cancellable_lockups_should_page_past_the_first_scan() {
+    let vars = SetupStreamTest::setup(12000);
+    let mut expected = vec![&vars.env];
+
+    // The budget of the test covers every call it makes
+    vars.env.budget().reset_unlimited();
+
+    for _ in 0..120 {
+        expected.push_back(create_stream(&vars, 0));
+    }
+
+    let (first, cursor) = vars.contract.get_cancellable_lockups(&vars.admin, &0, &100);
+
+    assert_eq!(first, expected.slice(0..100));
+    assert_eq!(cursor, 100);
+
+    let (second, cursor) = vars
+        .contract
+        .get_cancellable_lockups(&vars.admin, &cursor, &100);
+
+    assert_eq!(second, expected.slice(100..120));
+    assert_eq!(cursor, 0);
+}
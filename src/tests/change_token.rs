@@ -0,0 +1,63 @@
+use soroban_sdk::token;
+
+use crate::base::errors::CustomErrors;
+
+use super::setup::{SetupStreamTest, StreamFields};
+
+fn setup_new_token<'a>(vars: &SetupStreamTest<'a>) -> token::Client<'a> {
+    let token_id = vars.env.register_stellar_asset_contract(vars.admin.clone());
+    let new_token = token::Client::new(&vars.env, &token_id);
+
+    token::StellarAssetClient::new(&vars.env, &token_id).mint(&vars.admin, &1000);
+    new_token.approve(&vars.admin, &vars.contract.address, &1000, &6311000);
+
+    new_token
+}
+
+#[test]
+fn test_change_lockup_token_should_swap_the_token_before_the_start() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        start_date: 50,
+        end_date: 150,
+        cliff_date: 50,
+        ..Default::default()
+    });
+    let new_token = setup_new_token(&vars);
+
+    vars.move_ledger_timestamp_to(49);
+    vars.contract.change_lockup_token(&id, &new_token.address);
+
+    assert_eq!(vars.contract.get_lockup(&id).token, new_token.address);
+    assert_eq!(vars.token.balance(&vars.admin), 1000);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+    assert_eq!(new_token.balance(&vars.admin), 0);
+    assert_eq!(new_token.balance(&vars.contract.address), 1000);
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 0);
+    assert_eq!(vars.contract.get_locked_balance(&new_token.address), 1000);
+
+    vars.move_ledger_timestamp_to(150);
+
+    let receiver = vars.contract.get_lockup(&id).receiver;
+
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 1000);
+    assert_eq!(new_token.balance(&receiver), 1000);
+}
+
+#[test]
+fn test_change_lockup_token_should_revert_once_started() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        start_date: 50,
+        end_date: 150,
+        cliff_date: 50,
+        ..Default::default()
+    });
+    let new_token = setup_new_token(&vars);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let result = vars
+        .contract
+        .try_change_lockup_token(&id, &new_token.address);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupAlreadyStarted)));
+}
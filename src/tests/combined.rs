@@ -9,7 +9,7 @@ fn test_cancel_stream_should_transfer_when_withdrawn_is_not_zero() {
     vars.move_ledger_timestamp_to(50);
 
     let withdraw_amount = vars.contract.withdraw_lockup(&id, &200);
-    let amounts = vars.contract.cancel_lockup(&id);
+    let amounts = vars.contract.cancel_lockup(&id, &true);
     let stream = vars.contract.get_lockup(&id);
 
     assert_eq!(withdraw_amount, 200);
@@ -34,7 +34,7 @@ fn test_cancel_stream_should_not_transfer_when_stream_is_fully_withdrawn() {
     assert_eq!(vars.token.balance(&vars.admin.clone()), 0);
     assert_eq!(vars.token.balance(&stream.receiver.clone()), 1000);
 
-    let result = vars.contract.try_cancel_lockup(&id);
+    let result = vars.contract.try_cancel_lockup(&id, &true);
 
     assert_eq!(result, Err(Ok(errors::CustomErrors::LockupAlreadySettled)));
 }
@@ -0,0 +1,80 @@
+use soroban_sdk::{contract, contractimpl, Env};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{Condition, LockupInput, LockupOptions},
+};
+
+use super::setup::SetupStreamTest;
+
+#[contract]
+pub struct MockCondition;
+
+#[contractimpl]
+impl MockCondition {
+    pub fn set_released(e: Env, id: u64, released: bool) {
+        e.storage().persistent().set(&id, &released);
+    }
+
+    pub fn is_released(e: Env, id: u64) -> bool {
+        e.storage().persistent().get(&id).unwrap_or(false)
+    }
+}
+
+fn create_conditional_stream<'a>(vars: &SetupStreamTest<'a>) -> (u64, MockConditionClient<'a>) {
+    let condition =
+        MockConditionClient::new(&vars.env, &vars.env.register_contract(None, MockCondition));
+
+    let params = LockupInput {
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        options: LockupOptions {
+            condition: Condition::Some(condition.address.clone()),
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    (vars.contract.create_stream(&params), condition)
+}
+
+#[test]
+fn test_withdraw_should_be_blocked_until_the_condition_is_released() {
+    let vars = SetupStreamTest::setup(1000);
+    let (id, condition) = create_conditional_stream(&vars);
+    let receiver = vars.contract.get_lockup(&id).receiver;
+
+    vars.move_ledger_timestamp_to(100);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::ConditionNotMet)));
+    assert!(!vars.contract.can_withdraw(&id, &receiver));
+
+    condition.set_released(&id, &true);
+
+    assert!(vars.contract.can_withdraw(&id, &receiver));
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 1000);
+    assert_eq!(vars.token.balance(&receiver), 1000);
+}
+
+#[test]
+fn test_withdraw_should_be_blocked_again_when_the_condition_is_revoked() {
+    let vars = SetupStreamTest::setup(1000);
+    let (id, condition) = create_conditional_stream(&vars);
+
+    condition.set_released(&id, &true);
+
+    vars.move_ledger_timestamp_to(40);
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 400);
+
+    condition.set_released(&id, &false);
+
+    vars.move_ledger_timestamp_to(80);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::ConditionNotMet)));
+}
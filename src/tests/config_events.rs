@@ -0,0 +1,61 @@
+use soroban_sdk::{symbol_short, testutils::Address as _, testutils::Events, Address, IntoVal};
+
+use crate::base::types::{FeeConfig, FeeStrategy, FeeToken};
+
+use super::setup::SetupStreamTest;
+
+#[test]
+fn test_initialize_should_emit_admin_changed_event() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+
+    let events = vars.env.events().all();
+    assert!(events.contains((
+        vars.contract.address.clone(),
+        (symbol_short!("CONFIG"), symbol_short!("ADMIN")).into_val(&vars.env),
+        (vars.admin.clone(), 0u64).into_val(&vars.env)
+    )));
+}
+
+#[test]
+fn test_set_fee_config_should_emit_fee_changed_event() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+
+    let config = FeeConfig {
+        fee_bps: 50,
+        fee_recipient: Address::generate(&vars.env),
+        fee_token: FeeToken::None,
+        cancel_fee_bps: 0,
+        fee_strategy: FeeStrategy::None,
+    };
+
+    vars.contract.set_fee_config(&config);
+
+    let events = vars.env.events().all();
+    assert!(events.contains((
+        vars.contract.address.clone(),
+        (symbol_short!("CONFIG"), symbol_short!("FEE")).into_val(&vars.env),
+        (config, 1u64).into_val(&vars.env)
+    )));
+}
+
+#[test]
+fn test_set_position_nft_should_emit_nft_changed_event() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+
+    let nft = Address::generate(&vars.env);
+
+    vars.contract.set_position_nft(&nft);
+
+    let events = vars.env.events().all();
+    assert!(events.contains((
+        vars.contract.address.clone(),
+        (symbol_short!("CONFIG"), symbol_short!("NFT")).into_val(&vars.env),
+        (nft, 1u64).into_val(&vars.env)
+    )));
+}
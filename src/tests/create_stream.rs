@@ -1,7 +1,7 @@
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Events},
-    Address, IntoVal,
+    Address, IntoVal, TryFromVal,
 };
 
 use crate::{base::errors, tests::setup::SetupStreamTest};
@@ -23,11 +23,12 @@ fn test_stream_should_be_created() {
         start_date: now,
         end_date: now + 1000,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
     let id = vars.contract.create_stream(&params);
 
-    assert_eq!(id, 0);
+    assert_eq!(id, 1);
     assert_eq!(vars.token.decimals(), 7);
     assert_eq!(vars.token.balance(&vars.admin), 0);
     assert_eq!(vars.token.balance(&vars.contract.address), vars.amount);
@@ -50,13 +51,14 @@ fn test_stream_should_be_created_and_id_should_increment() {
         start_date: now,
         end_date: now + 1000,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
     assert_eq!(vars.contract.get_latest_lockup_id(), 0);
     let id = vars.contract.create_stream(&params);
     assert_eq!(vars.contract.get_latest_lockup_id(), 1);
 
-    assert_eq!(id, 0);
+    assert_eq!(id, 1);
     assert_eq!(vars.token.decimals(), 7);
     assert_eq!(vars.token.balance(&vars.admin), 0);
     assert_eq!(vars.token.balance(&vars.contract.address), vars.amount);
@@ -79,12 +81,17 @@ fn test_stream_should_be_created_and_id_should_increment_by_200() {
         start_date: now,
         end_date: now + 1000,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
+    // The budget of the test covers every call it makes
+    vars.env.budget().reset_unlimited();
+
     for i in 0..100 {
         assert_eq!(vars.contract.get_latest_lockup_id(), i);
-        vars.contract.create_stream(&params);
+        let id = vars.contract.create_stream(&params);
         assert_eq!(vars.contract.get_latest_lockup_id(), i + 1);
+        assert_eq!(id, i + 1);
     }
 }
 
@@ -92,12 +99,14 @@ fn test_stream_should_be_created_and_id_should_increment_by_200() {
 fn test_create_stream_should_emit_events() {
     let vars = SetupStreamTest::setup(2000);
 
+    vars.move_ledger_timestamp_to(1700000000);
+
     let receiver = Address::generate(&vars.env);
     let now = vars.env.ledger().timestamp();
 
     let params = crate::base::types::LockupInput {
         sender: vars.admin.clone(),
-        receiver,
+        receiver: receiver.clone(),
         token: vars.token.address.clone(),
         amount: vars.amount,
         cancellable_date: now,
@@ -105,6 +114,7 @@ fn test_create_stream_should_emit_events() {
         start_date: now,
         end_date: now + 1000,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
     vars.contract.create_stream(&params);
@@ -112,8 +122,8 @@ fn test_create_stream_should_emit_events() {
     let events = vars.env.events().all();
     assert!(events.contains((
         vars.contract.address.clone(),
-        (symbol_short!("LOCKUP"), symbol_short!("CREATED")).into_val(&vars.env),
-        0u64.into_val(&vars.env)
+        (symbol_short!("LOCKUP"), symbol_short!("CREATED"), receiver).into_val(&vars.env),
+        (1u64, 0u64, 1700000000u64).into_val(&vars.env)
     )));
 }
 
@@ -134,14 +144,15 @@ fn test_second_stream_should_have_incremented_id() {
         start_date: now,
         end_date: now + 1000,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
     let id0 = vars.contract.create_stream(&params);
     let id1 = vars.contract.create_stream(&params);
 
     assert_eq!(vars.token.balance(&vars.admin), 0);
-    assert_eq!(id0, 0);
-    assert_eq!(id1, 1);
+    assert_eq!(id0, 1);
+    assert_eq!(id1, 2);
 }
 
 #[test]
@@ -161,6 +172,7 @@ fn test_stream_should_revert_when_start_date_is_equal_to_end_date() {
         start_date: now,
         end_date: now,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
     assert_eq!(
@@ -186,6 +198,7 @@ fn test_stream_should_revert_when_start_date_is_greater_than_end_date() {
         start_date: now + 2,
         end_date: now,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
     assert_eq!(
@@ -211,6 +224,7 @@ fn test_stream_should_revert_when_cliff_date_is_less_than_start_date() {
         start_date: now + 100,
         end_date: now + 200,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
     assert_eq!(
@@ -219,6 +233,35 @@ fn test_stream_should_revert_when_cliff_date_is_less_than_start_date() {
     );
 }
 
+#[test]
+fn test_stream_should_revert_when_cliff_date_equals_end_date_in_strict_mode() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let receiver = Address::generate(&vars.env);
+    let now = vars.env.ledger().timestamp();
+
+    let mut params = crate::base::types::LockupInput {
+        receiver,
+        amount: vars.amount,
+        cliff_date: now + 200,
+        end_date: now + 200,
+        options: crate::base::types::LockupOptions {
+            strict_schedule: true,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    assert_eq!(
+        vars.contract.try_create_stream(&params),
+        Err(Ok(errors::CustomErrors::InvalidCliffDate))
+    );
+
+    params.options.strict_schedule = false;
+
+    assert_eq!(vars.contract.create_stream(&params), 1);
+}
+
 #[test]
 fn test_stream_should_revert_when_amount_is_zero() {
     let vars = SetupStreamTest::setup(2000);
@@ -236,6 +279,7 @@ fn test_stream_should_revert_when_amount_is_zero() {
         start_date: now,
         end_date: now,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
     assert_eq!(
@@ -261,6 +305,7 @@ fn test_stream_should_revert_when_amount_is_negative() {
         start_date: now,
         end_date: now,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
     assert_eq!(
@@ -285,6 +330,7 @@ fn test_stream_should_revert_when_sender_and_receiver_are_the_same_address() {
         start_date: now,
         end_date: now,
         rate: crate::base::types::Rate::Monthly,
+        options: Default::default(),
     };
 
     assert_eq!(
@@ -292,3 +338,132 @@ fn test_stream_should_revert_when_sender_and_receiver_are_the_same_address() {
         Err(Ok(errors::CustomErrors::InvalidReceiver))
     );
 }
+
+#[test]
+fn test_created_events_should_be_filterable_by_receiver() {
+    let vars = SetupStreamTest::setup(3000);
+
+    let receiver = Address::generate(&vars.env);
+    let other_receiver = Address::generate(&vars.env);
+    let now = vars.env.ledger().timestamp();
+
+    let mut params = crate::base::types::LockupInput {
+        receiver: receiver.clone(),
+        end_date: now + 86400,
+        rate: crate::base::types::Rate::Daily,
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_stream(&params);
+    vars.contract.create_vesting(&params);
+
+    params.receiver = other_receiver;
+    vars.contract.create_stream(&params);
+
+    let created_for_receiver = vars
+        .env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics.len() == 3
+                && Address::try_from_val(&vars.env, &topics.get_unchecked(2)).as_ref()
+                    == Ok(&receiver)
+        })
+        .count();
+
+    assert_eq!(created_for_receiver, 2);
+}
+
+#[test]
+fn test_stream_should_revert_when_sender_is_the_contract() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let params = crate::base::types::LockupInput {
+        sender: vars.contract.address.clone(),
+        amount: vars.amount,
+        ..vars.lockup_input()
+    };
+
+    assert_eq!(
+        vars.contract.try_create_stream(&params),
+        Err(Ok(errors::CustomErrors::InvalidSender))
+    );
+    assert_eq!(
+        vars.contract.try_create_vesting(&params),
+        Err(Ok(errors::CustomErrors::InvalidSender))
+    );
+}
+
+#[test]
+fn test_create_stream_by_rate_should_derive_the_amount_from_the_periods() {
+    let vars = SetupStreamTest::setup(3000);
+
+    let params = crate::base::types::LockupInput {
+        amount: 0,
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 30 * 86400,
+        rate: crate::base::types::Rate::Daily,
+        ..vars.lockup_input()
+    };
+
+    let id = vars.contract.create_stream_by_rate(&params, &100);
+
+    assert_eq!(vars.contract.get_lockup(&id).amount, 3000);
+    assert_eq!(vars.token.balance(&vars.contract.address), 3000);
+
+    vars.move_ledger_timestamp_to(86400);
+
+    assert_eq!(vars.contract.get_withdrawable(&id), 100);
+}
+
+#[test]
+fn test_create_stream_by_rate_should_revert_on_a_partial_period() {
+    let vars = SetupStreamTest::setup(3000);
+
+    let params = crate::base::types::LockupInput {
+        amount: 0,
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 30 * 86400 + 1,
+        rate: crate::base::types::Rate::Daily,
+        ..vars.lockup_input()
+    };
+
+    let result = vars.contract.try_create_stream_by_rate(&params, &100);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::InvalidDuration)));
+}
+
+#[test]
+fn test_create_stream_by_rate_should_keep_its_rate_over_the_default_rate() {
+    let vars = SetupStreamTest::setup(3000);
+
+    vars.initialize();
+    vars.contract
+        .set_default_rate(&crate::base::types::Rate::Weekly);
+
+    let params = crate::base::types::LockupInput {
+        amount: 0,
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 30 * 86400,
+        rate: crate::base::types::Rate::Daily,
+        options: crate::base::types::LockupOptions {
+            use_default_rate: true,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    let id = vars.contract.create_stream_by_rate(&params, &100);
+
+    assert_eq!(
+        vars.contract.get_lockup(&id).rate,
+        crate::base::types::Rate::Daily
+    );
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{testutils::Address as _, Address};
+use soroban_sdk::{testutils::Address as _, vec, Address};
 
 use super::setup::SetupStreamTest;
 use crate::base::{
@@ -24,12 +24,14 @@ fn test_create_vesting_should_work() {
         cancellable_date: now,
         token: vars.token.address,
         rate: crate::base::types::Rate::Daily,
+        options: Default::default(),
     };
 
     vars.contract.create_vesting(&params);
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_create_vesting_should_store_is_vesting_to_true() {
     let vars = SetupStreamTest::setup(1000);
 
@@ -47,6 +49,7 @@ fn test_create_vesting_should_store_is_vesting_to_true() {
         cancellable_date: now,
         token: vars.token.address,
         rate: crate::base::types::Rate::Daily,
+        options: Default::default(),
     };
 
     let id = vars.contract.create_vesting(&params);
@@ -74,6 +77,7 @@ fn test_create_vesting_should_revert_when_amount_is_negative() {
         cancellable_date: now,
         token: vars.token.address,
         rate: crate::base::types::Rate::Daily,
+        options: Default::default(),
     };
 
     let result = vars.contract.try_create_vesting(&params);
@@ -99,6 +103,7 @@ fn test_create_vesting_should_revert_when_amount_is_zero() {
         cancellable_date: now,
         token: vars.token.address,
         rate: crate::base::types::Rate::Daily,
+        options: Default::default(),
     };
 
     let result = vars.contract.try_create_vesting(&params);
@@ -123,6 +128,7 @@ fn test_create_vesting_should_revert_when_sender_and_receiver_are_the_same() {
         cancellable_date: now,
         token: vars.token.address,
         rate: crate::base::types::Rate::Daily,
+        options: Default::default(),
     };
 
     let result = vars.contract.try_create_vesting(&params);
@@ -148,6 +154,7 @@ fn test_create_vesting_should_revert_when_start_date_is_equal_to_end_date() {
         cancellable_date: now,
         token: vars.token.address,
         rate: crate::base::types::Rate::Daily,
+        options: Default::default(),
     };
 
     let result = vars.contract.try_create_vesting(&params);
@@ -173,6 +180,7 @@ fn test_create_vesting_should_revert_when_cancellable_date_is_greater_than_end_d
         cancellable_date: end_date + 1,
         token: vars.token.address,
         rate: crate::base::types::Rate::Daily,
+        options: Default::default(),
     };
 
     let result = vars.contract.try_create_vesting(&params);
@@ -182,3 +190,151 @@ fn test_create_vesting_should_revert_when_cancellable_date_is_greater_than_end_d
         Err(Ok(errors::CustomErrors::InvalidCancellableDate))
     );
 }
+
+#[test]
+fn test_create_vesting_should_revert_when_daily_unlock_rounds_to_zero() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let receiver = Address::generate(&vars.env);
+    let now = vars.env.ledger().timestamp();
+
+    let params = LockupInput {
+        receiver,
+        amount: 99,
+        end_date: now + Rate::Daily as u64 * 100,
+        rate: Rate::Daily,
+        ..vars.lockup_input()
+    };
+
+    let result = vars.contract.try_create_vesting(&params);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::RateTooGranular)));
+
+    let params = LockupInput {
+        amount: 100,
+        ..params
+    };
+
+    vars.contract.create_vesting(&params);
+}
+
+#[test]
+fn test_create_vesting_should_count_only_whole_periods_for_the_granularity() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let receiver = Address::generate(&vars.env);
+    let now = vars.env.ledger().timestamp();
+
+    // 100 whole weeks and a half, the half week unlocks with the end date
+    let params = LockupInput {
+        receiver,
+        amount: 99,
+        end_date: now + Rate::Weekly as u64 * 100 + Rate::Weekly as u64 / 2,
+        rate: Rate::Weekly,
+        ..vars.lockup_input()
+    };
+
+    let result = vars.contract.try_create_vesting(&params);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::RateTooGranular)));
+
+    let params = LockupInput {
+        amount: 100,
+        ..params
+    };
+
+    vars.contract.create_vesting(&params);
+}
+
+#[test]
+fn test_create_vesting_should_accept_every_rate() {
+    let rates = [
+        Rate::Daily,
+        Rate::Weekly,
+        Rate::Monthly,
+        Rate::Quarterly,
+        Rate::Annually,
+    ];
+
+    let vars = SetupStreamTest::setup(1000 * rates.len() as i128);
+
+    for rate in rates {
+        let params = LockupInput {
+            cancellable_date: 0,
+            cliff_date: 0,
+            start_date: 0,
+            end_date: rate as u64 * 4,
+            rate,
+            ..vars.lockup_input()
+        };
+
+        let id = vars.contract.create_vesting(&params);
+
+        assert_eq!(vars.contract.get_lockup(&id).rate, rate);
+    }
+}
+
+fn vesting_params(vars: &SetupStreamTest, end_date: u64, rate: Rate, amount: i128) -> LockupInput {
+    LockupInput {
+        amount,
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date,
+        rate,
+        ..vars.lockup_input()
+    }
+}
+
+#[test]
+fn test_preview_vesting_schedule_should_list_daily_steps() {
+    let vars = SetupStreamTest::setup(1000);
+    let day = Rate::Daily as u64;
+
+    let schedule = vars.contract.preview_vesting_schedule(&vesting_params(
+        &vars,
+        day * 5 / 2,
+        Rate::Daily,
+        1000,
+    ));
+
+    assert_eq!(
+        schedule,
+        vec![&vars.env, (day, 400), (2 * day, 800), (5 * day / 2, 1000)]
+    );
+    assert_eq!(vars.contract.get_latest_lockup_id(), 0);
+}
+
+#[test]
+fn test_preview_vesting_schedule_should_list_monthly_steps() {
+    let vars = SetupStreamTest::setup(1000);
+    let month = Rate::Monthly as u64;
+
+    let schedule = vars.contract.preview_vesting_schedule(&vesting_params(
+        &vars,
+        month * 3,
+        Rate::Monthly,
+        900,
+    ));
+
+    assert_eq!(
+        schedule,
+        vec![&vars.env, (month, 300), (2 * month, 600), (3 * month, 900)]
+    );
+}
+
+#[test]
+fn test_preview_vesting_schedule_should_cap_the_steps() {
+    let vars = SetupStreamTest::setup(1000);
+    let day = Rate::Daily as u64;
+
+    let schedule = vars.contract.preview_vesting_schedule(&vesting_params(
+        &vars,
+        day * 365,
+        Rate::Daily,
+        36500,
+    ));
+
+    assert_eq!(schedule.len(), 100);
+    assert_eq!(schedule.get_unchecked(99), (100 * day, 10000));
+}
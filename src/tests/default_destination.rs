@@ -0,0 +1,120 @@
+use soroban_sdk::{testutils::Address as _, Address};
+
+use super::setup::{SetupStreamTest, StreamFields};
+
+#[test]
+fn test_set_default_destination_should_be_authorized_by_receiver() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let stream = vars.contract.get_lockup(&id);
+    let destination = Address::generate(&vars.env);
+
+    vars.contract.set_default_destination(&id, &destination);
+
+    let authorizers: super::std::vec::Vec<Address> = vars
+        .env
+        .auths()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+
+    assert_eq!(authorizers, [stream.receiver]);
+    assert_eq!(
+        vars.contract.get_default_destination(&id),
+        Some(destination)
+    );
+}
+
+#[test]
+fn test_withdraw_stream_should_pay_the_default_destination() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let stream = vars.contract.get_lockup(&id);
+    let destination = Address::generate(&vars.env);
+
+    vars.contract.set_default_destination(&id, &destination);
+    vars.move_ledger_timestamp_to(50);
+
+    let amount = vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(amount, 500);
+    assert_eq!(vars.token.balance(&destination), 500);
+    assert_eq!(vars.token.balance(&stream.receiver), 0);
+    assert_eq!(vars.contract.get_lockup(&id).withdrawn, 500);
+}
+
+#[test]
+fn test_withdraw_stream_should_pay_the_receiver_after_clearing_the_default_destination() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let stream = vars.contract.get_lockup(&id);
+    let destination = Address::generate(&vars.env);
+
+    vars.contract.set_default_destination(&id, &destination);
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    vars.contract.clear_default_destination(&id);
+    vars.move_ledger_timestamp_to(100);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.contract.get_default_destination(&id), None);
+    assert_eq!(vars.token.balance(&destination), 500);
+    assert_eq!(vars.token.balance(&stream.receiver), 500);
+}
+
+#[test]
+fn test_transfer_lockup_receiver_should_clear_the_default_destination() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let destination = Address::generate(&vars.env);
+    let new_receiver = Address::generate(&vars.env);
+
+    vars.contract.set_default_destination(&id, &destination);
+    vars.contract.transfer_lockup_receiver(&id, &new_receiver);
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.contract.get_default_destination(&id), None);
+    assert_eq!(vars.token.balance(&destination), 0);
+    assert_eq!(vars.token.balance(&new_receiver), 500);
+}
+
+#[test]
+fn test_finalize_lockup_should_pay_the_default_destination() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let stream = vars.contract.get_lockup(&id);
+    let destination = Address::generate(&vars.env);
+
+    vars.contract.set_default_destination(&id, &destination);
+    vars.move_ledger_timestamp_to(100);
+
+    assert_eq!(vars.contract.finalize_lockup(&id), 1000);
+    assert_eq!(vars.token.balance(&destination), 1000);
+    assert_eq!(vars.token.balance(&stream.receiver), 0);
+}
+
+#[test]
+fn test_cancel_lockup_should_push_to_the_default_destination() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let stream = vars.contract.get_lockup(&id);
+    let destination = Address::generate(&vars.env);
+
+    vars.contract.set_default_destination(&id, &destination);
+    vars.move_ledger_timestamp_to(30);
+
+    assert_eq!(vars.contract.cancel_lockup(&id, &true), (700, 300));
+    assert_eq!(vars.token.balance(&destination), 300);
+    assert_eq!(vars.token.balance(&stream.receiver), 0);
+    assert_eq!(vars.token.balance(&stream.sender), 700);
+}
+
+#[test]
+fn test_settle_lockup_should_pay_the_default_destination() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let stream = vars.contract.get_lockup(&id);
+    let destination = Address::generate(&vars.env);
+
+    vars.contract.set_default_destination(&id, &destination);
+    vars.contract.settle_lockup(&id, &400);
+
+    assert_eq!(vars.token.balance(&destination), 400);
+    assert_eq!(vars.token.balance(&stream.receiver), 0);
+    assert_eq!(vars.token.balance(&stream.sender), 600);
+}
@@ -0,0 +1,66 @@
+use crate::base::types::{LockupInput, LockupOptions, Rate};
+
+use super::setup::SetupStreamTest;
+
+const DAY: u64 = 86400;
+const NOON: u64 = DAY / 2;
+
+fn create_daily_vesting(vars: &SetupStreamTest, align_to_epoch: bool) -> u64 {
+    let params = LockupInput {
+        cancellable_date: 0,
+        cliff_date: NOON,
+        start_date: NOON,
+        end_date: NOON + 10 * DAY,
+        rate: Rate::Daily,
+        options: LockupOptions {
+            align_to_epoch,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_vesting(&params)
+}
+
+#[test]
+fn test_vesting_should_step_from_the_start_date_by_default() {
+    let vars = SetupStreamTest::setup(1000);
+    let id = create_daily_vesting(&vars, false);
+
+    vars.move_ledger_timestamp_to(NOON);
+    assert_eq!(vars.contract.get_next_unlock(&id), (NOON + DAY, 100));
+
+    vars.move_ledger_timestamp_to(2 * DAY - 1);
+    assert_eq!(vars.contract.get_withdrawable(&id), 100);
+
+    vars.move_ledger_timestamp_to(10 * DAY);
+    assert_eq!(vars.contract.get_withdrawable(&id), 900);
+    assert_eq!(vars.contract.get_next_unlock(&id), (NOON + 10 * DAY, 100));
+}
+
+#[test]
+fn test_vesting_should_step_on_epoch_boundaries_when_aligned() {
+    let vars = SetupStreamTest::setup(1000);
+    let id = create_daily_vesting(&vars, true);
+
+    // The first step only unlocks the half day between the start and midnight
+    vars.move_ledger_timestamp_to(NOON);
+    assert_eq!(vars.contract.get_next_unlock(&id), (DAY, 50));
+
+    vars.move_ledger_timestamp_to(DAY);
+    assert_eq!(vars.contract.get_withdrawable(&id), 50);
+
+    vars.move_ledger_timestamp_to(2 * DAY - 1);
+    assert_eq!(vars.contract.get_withdrawable(&id), 50);
+
+    vars.move_ledger_timestamp_to(2 * DAY);
+    assert_eq!(vars.contract.get_withdrawable(&id), 150);
+
+    // The last step unlocks the half day between midnight and the end date
+    vars.move_ledger_timestamp_to(10 * DAY);
+    assert_eq!(vars.contract.get_withdrawable(&id), 950);
+    assert_eq!(vars.contract.get_next_unlock(&id), (NOON + 10 * DAY, 50));
+
+    vars.move_ledger_timestamp_to(NOON + 10 * DAY);
+    assert_eq!(vars.contract.get_withdrawable(&id), 1000);
+}
@@ -0,0 +1,68 @@
+use soroban_sdk::BytesN;
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{ExternalRef, LockupInput, LockupOptions},
+};
+
+use super::setup::SetupStreamTest;
+
+fn stream_params(vars: &SetupStreamTest, external_ref: ExternalRef) -> LockupInput {
+    LockupInput {
+        amount: 500,
+        options: LockupOptions {
+            external_ref,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    }
+}
+
+#[test]
+fn test_lockup_should_be_found_by_external_ref() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let external_ref = BytesN::from_array(&vars.env, &[7; 32]);
+
+    vars.contract
+        .create_stream(&stream_params(&vars, ExternalRef::None));
+    let id = vars.contract.create_stream(&stream_params(
+        &vars,
+        ExternalRef::Some(external_ref.clone()),
+    ));
+
+    assert_eq!(vars.contract.get_lockup_by_external_ref(&external_ref), id);
+    assert_eq!(
+        vars.contract.get_lockup(&id).external_ref,
+        ExternalRef::Some(external_ref)
+    );
+}
+
+#[test]
+fn test_create_should_revert_when_external_ref_is_duplicated() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let external_ref = BytesN::from_array(&vars.env, &[7; 32]);
+
+    vars.contract.create_stream(&stream_params(
+        &vars,
+        ExternalRef::Some(external_ref.clone()),
+    ));
+    let result = vars
+        .contract
+        .try_create_vesting(&stream_params(&vars, ExternalRef::Some(external_ref)));
+
+    assert_eq!(result, Err(Ok(CustomErrors::DuplicateExternalRef)));
+    assert_eq!(vars.token.balance(&vars.admin), 500);
+}
+
+#[test]
+fn test_get_lockup_by_external_ref_should_revert_when_ref_is_unknown() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let external_ref = BytesN::from_array(&vars.env, &[7; 32]);
+
+    let result = vars.contract.try_get_lockup_by_external_ref(&external_ref);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupNotFound)));
+}
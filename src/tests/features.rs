@@ -0,0 +1,37 @@
+use soroban_sdk::{testutils::Address as _, Address};
+
+use crate::base::types::{
+    FeeConfig, FeeStrategy, FeeToken, FEATURE_FEES, FEATURE_TEMPLATES, FEATURE_TOKENIZED_POSITIONS,
+};
+
+use super::setup::SetupStreamTest;
+
+#[test]
+fn test_get_features_should_flip_bits_as_features_are_enabled() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+
+    assert_eq!(vars.contract.get_features(), FEATURE_TEMPLATES);
+
+    vars.contract.set_fee_config(&FeeConfig {
+        fee_bps: 10,
+        fee_recipient: Address::generate(&vars.env),
+        fee_token: FeeToken::None,
+        cancel_fee_bps: 0,
+        fee_strategy: FeeStrategy::None,
+    });
+
+    assert_eq!(
+        vars.contract.get_features(),
+        FEATURE_FEES | FEATURE_TEMPLATES
+    );
+
+    vars.contract
+        .set_position_nft(&Address::generate(&vars.env));
+
+    assert_eq!(
+        vars.contract.get_features(),
+        FEATURE_FEES | FEATURE_TEMPLATES | FEATURE_TOKENIZED_POSITIONS
+    );
+}
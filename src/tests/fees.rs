@@ -0,0 +1,256 @@
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, token, Address, Env};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{FeeConfig, FeeStrategy, FeeToken, LockupInput, LockupOptions},
+};
+
+use super::setup::SetupStreamTest;
+
+#[contract]
+pub struct MockFeeStrategy;
+
+#[contractimpl]
+impl MockFeeStrategy {
+    pub fn compute_fee(_e: Env, _token: Address, amount: i128, duration: u64) -> i128 {
+        amount / 100 + duration as i128 / 10
+    }
+}
+
+fn stream_params(vars: &SetupStreamTest, amount: i128) -> LockupInput {
+    LockupInput {
+        amount,
+        ..vars.lockup_input()
+    }
+}
+
+fn set_fee_config(vars: &SetupStreamTest, fee_token: FeeToken) -> Address {
+    let fee_recipient = Address::generate(&vars.env);
+
+    vars.initialize();
+    vars.contract.set_fee_config(&FeeConfig {
+        fee_bps: 100,
+        fee_recipient: fee_recipient.clone(),
+        fee_token,
+        cancel_fee_bps: 0,
+        fee_strategy: FeeStrategy::None,
+    });
+
+    fee_recipient
+}
+
+#[test]
+fn test_creation_fee_should_be_skimmed_from_the_amount() {
+    let vars = SetupStreamTest::setup(1000);
+    let fee_recipient = set_fee_config(&vars, FeeToken::None);
+
+    let id = vars.contract.create_stream(&stream_params(&vars, 1000));
+    let lockup = vars.contract.get_lockup(&id);
+
+    assert_eq!(lockup.amount, 990);
+    assert_eq!(lockup.funded_amount, 990);
+    assert_eq!(vars.token.balance(&fee_recipient), 10);
+    assert_eq!(vars.token.balance(&vars.contract.address), 990);
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 990);
+
+    vars.move_ledger_timestamp_to(100);
+
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 990);
+}
+
+#[test]
+fn test_fees_collected_should_add_up_the_skimmed_creation_fees() {
+    let vars = SetupStreamTest::setup(3000);
+
+    assert_eq!(vars.contract.get_fees_collected(&vars.token.address), 0);
+
+    let fee_recipient = set_fee_config(&vars, FeeToken::None);
+
+    vars.contract.create_stream(&stream_params(&vars, 1000));
+
+    assert_eq!(vars.contract.get_fees_collected(&vars.token.address), 10);
+
+    vars.contract.create_stream(&stream_params(&vars, 2000));
+
+    assert_eq!(vars.contract.get_fees_collected(&vars.token.address), 30);
+    assert_eq!(vars.token.balance(&fee_recipient), 30);
+}
+
+#[test]
+fn test_creation_fee_should_be_pulled_in_the_fee_token() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let fee_token_id = vars.env.register_stellar_asset_contract(vars.admin.clone());
+    let fee_token = token::Client::new(&vars.env, &fee_token_id);
+
+    token::StellarAssetClient::new(&vars.env, &fee_token_id).mint(&vars.admin, &50);
+    fee_token.approve(&vars.admin, &vars.contract.address, &50, &6311000);
+
+    let fee_recipient = set_fee_config(&vars, FeeToken::Some(fee_token_id.clone()));
+
+    let id = vars.contract.create_stream(&stream_params(&vars, 1000));
+    let lockup = vars.contract.get_lockup(&id);
+
+    assert_eq!(lockup.amount, 1000);
+    assert_eq!(vars.token.balance(&vars.contract.address), 1000);
+    assert_eq!(vars.token.balance(&fee_recipient), 0);
+    assert_eq!(fee_token.balance(&fee_recipient), 10);
+    assert_eq!(fee_token.balance(&vars.admin), 40);
+    assert_eq!(vars.contract.get_fees_collected(&fee_token_id), 10);
+    assert_eq!(vars.contract.get_fees_collected(&vars.token.address), 0);
+}
+
+#[test]
+fn test_creation_fee_in_the_fee_token_should_be_authorized_by_the_sender() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let fee_token_id = vars.env.register_stellar_asset_contract(vars.admin.clone());
+    let fee_token = token::Client::new(&vars.env, &fee_token_id);
+
+    token::StellarAssetClient::new(&vars.env, &fee_token_id).mint(&vars.admin, &50);
+    fee_token.approve(&vars.admin, &vars.contract.address, &50, &6311000);
+
+    let fee_recipient = set_fee_config(&vars, FeeToken::Some(fee_token_id));
+
+    let params = LockupInput {
+        options: LockupOptions {
+            initial_deposit: Some(0),
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_stream(&params);
+
+    let authorizers: super::std::vec::Vec<Address> = vars
+        .env
+        .auths()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+
+    assert_eq!(authorizers, [params.sender]);
+    assert_eq!(fee_token.balance(&fee_recipient), 10);
+}
+
+#[test]
+fn test_creation_fee_should_revert_when_fee_allowance_is_insufficient() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let fee_token_id = vars.env.register_stellar_asset_contract(vars.admin.clone());
+
+    token::StellarAssetClient::new(&vars.env, &fee_token_id).mint(&vars.admin, &50);
+    token::Client::new(&vars.env, &fee_token_id).approve(
+        &vars.admin,
+        &vars.contract.address,
+        &9,
+        &6311000,
+    );
+
+    set_fee_config(&vars, FeeToken::Some(fee_token_id));
+
+    let result = vars.contract.try_create_stream(&stream_params(&vars, 1000));
+
+    assert_eq!(result, Err(Ok(CustomErrors::InsufficientFeeAllowance)));
+}
+
+#[test]
+fn test_creation_fee_should_revert_when_the_fee_overflows() {
+    let vars = SetupStreamTest::setup(1000);
+    set_fee_config(&vars, FeeToken::None);
+
+    let result = vars
+        .contract
+        .try_create_stream(&stream_params(&vars, i128::MAX / 50));
+
+    assert_eq!(result, Err(Ok(CustomErrors::AmountOverflows)));
+}
+
+#[test]
+fn test_set_fee_config_should_revert_when_fee_bps_is_too_high() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+
+    let result = vars.contract.try_set_fee_config(&FeeConfig {
+        fee_bps: 10001,
+        fee_recipient: Address::generate(&vars.env),
+        fee_token: FeeToken::None,
+        cancel_fee_bps: 0,
+        fee_strategy: FeeStrategy::None,
+    });
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidFeeConfig)));
+    assert_eq!(vars.contract.get_fee_config(), None);
+}
+
+#[test]
+fn test_set_fee_config_should_revert_when_cancel_fee_bps_is_too_high() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+
+    let result = vars.contract.try_set_fee_config(&FeeConfig {
+        fee_bps: 0,
+        fee_recipient: Address::generate(&vars.env),
+        fee_token: FeeToken::None,
+        cancel_fee_bps: 10001,
+        fee_strategy: FeeStrategy::None,
+    });
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidFeeConfig)));
+}
+
+#[test]
+fn test_cancellation_fee_should_be_taken_from_the_sender_refund() {
+    let vars = SetupStreamTest::setup(1000);
+    let fee_recipient = Address::generate(&vars.env);
+
+    let params = stream_params(&vars, 1000);
+    let receiver = params.receiver.clone();
+    let id = vars.contract.create_stream(&params);
+
+    vars.initialize();
+    vars.contract.set_fee_config(&FeeConfig {
+        fee_bps: 0,
+        fee_recipient: fee_recipient.clone(),
+        fee_token: FeeToken::None,
+        cancel_fee_bps: 1000,
+        fee_strategy: FeeStrategy::None,
+    });
+
+    vars.move_ledger_timestamp_to(40);
+
+    let sender_balance = vars.token.balance(&vars.admin);
+
+    assert_eq!(vars.contract.get_refundable_to_sender(&id), 540);
+    assert_eq!(vars.contract.cancel_lockup(&id, &true), (540, 400));
+    assert_eq!(vars.token.balance(&vars.admin), sender_balance + 540);
+    assert_eq!(vars.token.balance(&receiver), 400);
+    assert_eq!(vars.token.balance(&fee_recipient), 60);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+    assert_eq!(vars.contract.get_fees_collected(&vars.token.address), 60);
+}
+
+#[test]
+fn test_creation_fee_should_be_computed_by_the_fee_strategy() {
+    let vars = SetupStreamTest::setup(1000);
+    let fee_recipient = Address::generate(&vars.env);
+    let strategy = vars.env.register_contract(None, MockFeeStrategy);
+
+    vars.initialize();
+    vars.contract.set_fee_config(&FeeConfig {
+        fee_bps: 5000,
+        fee_recipient: fee_recipient.clone(),
+        fee_token: FeeToken::None,
+        cancel_fee_bps: 0,
+        fee_strategy: FeeStrategy::Some(strategy),
+    });
+
+    let id = vars.contract.create_stream(&stream_params(&vars, 1000));
+    let lockup = vars.contract.get_lockup(&id);
+
+    assert_eq!(lockup.amount, 980);
+    assert_eq!(vars.token.balance(&fee_recipient), 20);
+    assert_eq!(vars.token.balance(&vars.contract.address), 980);
+}
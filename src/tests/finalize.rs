@@ -0,0 +1,191 @@
+use soroban_sdk::{symbol_short, testutils::Events, IntoVal};
+
+use crate::base::{errors::CustomErrors, types::LockupStatus};
+
+use super::setup::{SetupStreamTest, StreamFields};
+
+#[test]
+fn test_get_lockup_status_should_be_settled_without_emitting_events() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(100);
+    assert_eq!(vars.contract.get_lockup_status(&id), LockupStatus::Ended);
+
+    vars.contract.withdraw_lockup(&id, &0);
+
+    let events_count = vars.env.events().all().len();
+
+    assert_eq!(vars.contract.get_lockup_status(&id), LockupStatus::Settled);
+    assert_eq!(vars.env.events().all().len(), events_count);
+}
+
+#[test]
+fn test_finalize_if_settled_should_emit_settled_event() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(100);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert!(vars.contract.finalize_if_settled(&id));
+
+    let events = vars.env.events().all();
+    assert!(events.contains((
+        vars.contract.address.clone(),
+        (symbol_short!("LOCKUP"), symbol_short!("SETTLED")).into_val(&vars.env),
+        (id, 2u64).into_val(&vars.env)
+    )));
+}
+
+#[test]
+fn test_finalize_if_settled_should_settle_cancelled_and_drained_lockups() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.cancel_lockup(&id, &false);
+
+    assert!(!vars.contract.finalize_if_settled(&id));
+
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert!(vars.contract.finalize_if_settled(&id));
+}
+
+#[test]
+fn test_finalize_if_settled_should_not_settle_active_or_undrained_lockups() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+    assert!(!vars.contract.finalize_if_settled(&id));
+
+    vars.move_ledger_timestamp_to(100);
+    assert!(!vars.contract.finalize_if_settled(&id));
+
+    let events = vars.env.events().all();
+    assert!(!events.contains((
+        vars.contract.address.clone(),
+        (symbol_short!("LOCKUP"), symbol_short!("SETTLED")).into_val(&vars.env),
+        (id, 1u64).into_val(&vars.env)
+    )));
+}
+
+#[test]
+fn test_cancel_lockup_should_revert_after_end_date() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(100);
+
+    let result = vars.contract.try_cancel_lockup(&id, &true);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupAlreadySettled)));
+}
+
+#[test]
+fn test_finalize_lockup_should_push_the_remainder_to_the_receiver() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let receiver = vars.contract.get_lockup(&id).receiver;
+
+    vars.move_ledger_timestamp_to(30);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    vars.move_ledger_timestamp_to(150);
+
+    assert_eq!(vars.contract.finalize_lockup(&id), 700);
+    assert_eq!(vars.token.balance(&receiver), 1000);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+    assert_eq!(vars.contract.get_lockup_status(&id), LockupStatus::Settled);
+    assert_eq!(vars.contract.finalize_lockup(&id), 0);
+}
+
+#[test]
+fn test_finalize_lockup_should_revert_before_end_date() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(99);
+
+    let result = vars.contract.try_finalize_lockup(&id);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupNotEndedYet)));
+}
+
+#[test]
+fn test_is_lockup_settled_should_be_true_once_ended_and_withdrawn() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.withdraw_lockup(&id, &0);
+    assert!(!vars.contract.is_lockup_settled(&id));
+
+    vars.move_ledger_timestamp_to(100);
+    assert!(!vars.contract.is_lockup_settled(&id));
+
+    vars.contract.withdraw_lockup(&id, &0);
+    assert!(vars.contract.is_lockup_settled(&id));
+}
+
+#[test]
+fn test_is_lockup_settled_should_be_true_once_cancelled_and_drained() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.cancel_lockup(&id, &false);
+    assert!(!vars.contract.is_lockup_settled(&id));
+
+    vars.contract.withdraw_lockup(&id, &0);
+    assert!(vars.contract.is_lockup_settled(&id));
+}
+
+#[test]
+fn test_is_lockup_settled_should_be_true_when_cancelled_with_the_push() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.cancel_lockup(&id, &true);
+
+    assert!(vars.contract.is_lockup_settled(&id));
+}
+
+#[test]
+fn test_get_active_count_should_count_lockups_until_they_are_withdrawn() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    assert_eq!(vars.contract.get_active_count(), 1);
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.contract.get_active_count(), 1);
+
+    vars.move_ledger_timestamp_to(100);
+
+    assert_eq!(vars.contract.get_active_count(), 1);
+
+    vars.contract.withdraw_lockup(&id, &0);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.contract.get_active_count(), 0);
+
+    vars.contract.finalize_if_settled(&id);
+
+    assert_eq!(vars.contract.get_active_count(), 0);
+}
+
+#[test]
+fn test_get_active_count_should_count_cancelled_lockups_until_they_are_drained() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.cancel_lockup(&id, &false);
+
+    assert_eq!(vars.contract.get_active_count(), 1);
+
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.contract.get_active_count(), 0);
+
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.cancel_lockup(&id, &true);
+
+    assert_eq!(vars.contract.get_active_count(), 0);
+}
@@ -0,0 +1,182 @@
+use soroban_sdk::{testutils::Address as _, Address};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{LockupInput, LockupOptions},
+};
+
+use super::setup::{SetupStreamTest, StreamFields};
+
+fn create_underfunded_stream(vars: &SetupStreamTest, initial_deposit: i128) -> u64 {
+    let receiver = Address::generate(&vars.env);
+
+    let params = LockupInput {
+        receiver,
+        options: LockupOptions {
+            initial_deposit: Some(initial_deposit),
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_stream(&params)
+}
+
+#[test]
+fn test_create_stream_should_only_pull_the_initial_deposit() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let id = create_underfunded_stream(&vars, 400);
+    let stream = vars.contract.get_lockup(&id);
+
+    assert_eq!(stream.amount, 1000);
+    assert_eq!(stream.funded_amount, 400);
+    assert_eq!(vars.token.balance(&vars.admin), 600);
+    assert_eq!(vars.token.balance(&vars.contract.address), 400);
+}
+
+#[test]
+fn test_create_stream_should_revert_when_initial_deposit_is_greater_than_amount() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let receiver = Address::generate(&vars.env);
+
+    let params = LockupInput {
+        receiver,
+        options: LockupOptions {
+            initial_deposit: Some(1001),
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    let result = vars.contract.try_create_stream(&params);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidDeposit)));
+}
+
+#[test]
+fn test_withdraw_should_revert_when_amount_is_vested_but_not_funded() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let id = create_underfunded_stream(&vars, 400);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &450);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InsufficientFunding)));
+}
+
+#[test]
+fn test_withdraw_should_be_capped_at_the_funded_amount() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let id = create_underfunded_stream(&vars, 400);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let amount = vars.contract.withdraw_lockup(&id, &0);
+    let stream = vars.contract.get_lockup(&id);
+
+    assert_eq!(amount, 400);
+    assert_eq!(vars.token.balance(&stream.receiver), 400);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InsufficientFunding)));
+}
+
+#[test]
+fn test_fund_lockup_should_enable_withdrawal() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let id = create_underfunded_stream(&vars, 400);
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    let funded = vars.contract.fund_lockup(&id, &600);
+    assert_eq!(funded, 1000);
+    assert_eq!(vars.token.balance(&vars.admin), 0);
+
+    let amount = vars.contract.withdraw_lockup(&id, &0);
+    let stream = vars.contract.get_lockup(&id);
+
+    assert_eq!(amount, 100);
+    assert_eq!(vars.token.balance(&stream.receiver), 500);
+    assert_eq!(vars.token.balance(&vars.contract.address), 500);
+}
+
+#[test]
+fn test_fund_lockup_should_revert_when_funding_exceeds_amount() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let id = create_underfunded_stream(&vars, 400);
+
+    let result = vars.contract.try_fund_lockup(&id, &601);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidAmount)));
+}
+
+#[test]
+fn test_cancel_should_pay_the_receiver_first_when_underfunded() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let id = create_underfunded_stream(&vars, 400);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let (sender_amount, receiver_amount) = vars.contract.cancel_lockup(&id, &true);
+
+    assert_eq!(sender_amount, 0);
+    assert_eq!(receiver_amount, 400);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+}
+
+#[test]
+fn test_get_lockup_funding_should_return_funded_and_total_amounts() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let id = create_underfunded_stream(&vars, 400);
+
+    assert_eq!(vars.contract.get_lockup_funding(&id), (400, 1000));
+
+    vars.contract.fund_lockup(&id, &250);
+
+    assert_eq!(vars.contract.get_lockup_funding(&id), (650, 1000));
+}
+
+#[test]
+fn test_get_lockup_funding_should_be_equal_for_fully_funded_lockups() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    assert_eq!(
+        vars.contract.get_lockup_funding(&id),
+        (vars.amount, vars.amount)
+    );
+}
+
+#[test]
+fn test_create_stream_without_deposit_should_be_authorized_by_the_sender() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = LockupInput {
+        options: LockupOptions {
+            initial_deposit: Some(0),
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_stream(&params);
+
+    let authorizers: super::std::vec::Vec<Address> = vars
+        .env
+        .auths()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+
+    assert_eq!(authorizers, [params.sender]);
+}
@@ -1,6 +1,12 @@
-use crate::base::errors::CustomErrors;
+use soroban_sdk::{vec, xdr::FromXdr};
 
-use super::setup::{SetupStreamTest, StreamFields};
+use crate::base::{
+    data_key::DataKey,
+    errors::CustomErrors,
+    types::{CliffMode, Lockup, LockupInput, LockupKind, LockupOptions, Rate},
+};
+
+use super::setup::{SetupStreamTest, StreamFields, VestingFields};
 
 #[test]
 fn test_get_stream_should_return_the_correct_data() {
@@ -15,7 +21,270 @@ fn test_get_stream_should_return_the_correct_data() {
 fn test_get_stream_should_revert_when_stream_does_not_exist() {
     let (vars, _) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
 
-    let stream = vars.contract.try_get_lockup(&1);
+    let stream = vars.contract.try_get_lockup(&2);
 
     assert_eq!(stream, Err(Ok(CustomErrors::LockupNotFound)));
 }
+
+#[test]
+fn test_get_stream_should_revert_for_the_reserved_zero_id() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    assert_eq!(id, 1);
+
+    let stream = vars.contract.try_get_lockup(&0);
+
+    assert_eq!(stream, Err(Ok(CustomErrors::LockupNotFound)));
+}
+
+#[test]
+fn test_get_lockup_kind_should_return_linear_for_streams() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    assert_eq!(vars.contract.get_lockup_kind(&id), LockupKind::Linear);
+}
+
+#[test]
+fn test_get_lockup_kind_should_return_vesting_for_vestings() {
+    let (vars, id) = SetupStreamTest::setup_with_vesting_created(VestingFields::default());
+
+    assert_eq!(vars.contract.get_lockup_kind(&id), LockupKind::Vesting);
+}
+
+#[test]
+fn test_get_lockup_kind_should_revert_when_lockup_does_not_exist() {
+    let (vars, _) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let result = vars.contract.try_get_lockup_kind(&2);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupNotFound)));
+}
+
+#[test]
+fn test_has_cliff_should_be_false_without_a_cliff() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    assert!(!vars.contract.has_cliff(&id));
+}
+
+#[test]
+fn test_has_cliff_should_be_true_with_a_cliff_after_the_start() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        start_date: 10,
+        cliff_date: 30,
+        ..Default::default()
+    });
+
+    assert!(vars.contract.has_cliff(&id));
+}
+
+#[test]
+fn test_get_lockups_should_return_none_for_missing_and_corrupted_lockups() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.env.as_contract(&vars.contract.address, || {
+        vars.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Lockup(5), &42u32);
+    });
+
+    let lockups = vars.contract.get_lockups(&vec![&vars.env, id, 3, 5, id]);
+
+    assert_eq!(lockups.len(), 4);
+    assert_eq!(
+        lockups.get_unchecked(0),
+        Some(vars.contract.get_lockup(&id))
+    );
+    assert_eq!(lockups.get_unchecked(1), None);
+    assert_eq!(lockups.get_unchecked(2), None);
+    assert_eq!(
+        lockups.get_unchecked(3),
+        Some(vars.contract.get_lockup(&id))
+    );
+}
+
+#[test]
+fn test_get_lockups_should_revert_when_batch_is_too_large() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let mut ids = vec![&vars.env];
+
+    for id in 0..21 {
+        ids.push_back(id);
+    }
+
+    let result = vars.contract.try_get_lockups(&ids);
+
+    assert_eq!(result, Err(Ok(CustomErrors::BatchTooLarge)));
+}
+
+#[test]
+fn test_get_lockup_flat_should_match_the_lockup() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        start_date: 10,
+        cliff_date: 20,
+        cancellable_date: 30,
+        end_date: 110,
+        ..Default::default()
+    });
+
+    vars.move_ledger_timestamp_to(60);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    let lockup = vars.contract.get_lockup(&id);
+    let flat = vars.contract.get_lockup_flat(&id);
+
+    assert_eq!(
+        flat,
+        (
+            lockup.sender,
+            lockup.receiver,
+            lockup.token,
+            1000,
+            500,
+            10,
+            110,
+            20,
+            30,
+            false,
+            false
+        )
+    );
+}
+
+#[test]
+fn test_get_lockup_amount_scaled_should_return_amount_and_decimals() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    assert_eq!(vars.contract.get_lockup_amount_scaled(&id), (1000, 7));
+}
+
+#[test]
+fn test_get_lockup_input_should_rebuild_the_creation_input() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = LockupInput {
+        cancellable_date: 30,
+        cliff_date: 20,
+        start_date: 10,
+        end_date: 110,
+        rate: Rate::Weekly,
+        options: LockupOptions {
+            withdraw_unlock_date: Some(50),
+            cliff_mode: CliffMode::LinearFromCliff,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    let id = vars.contract.create_stream(&params);
+    let input = vars.contract.get_lockup_input(&id);
+
+    assert_eq!(input.sender, params.sender);
+    assert_eq!(input.receiver, params.receiver);
+    assert_eq!(input.token, params.token);
+    assert_eq!(input.amount, params.amount);
+    assert_eq!(input.cancellable_date, params.cancellable_date);
+    assert_eq!(input.cliff_date, params.cliff_date);
+    assert_eq!(input.start_date, params.start_date);
+    assert_eq!(input.end_date, params.end_date);
+    assert_eq!(input.rate, params.rate);
+    assert_eq!(input.options, params.options);
+}
+
+#[test]
+fn test_export_lockup_should_round_trip_through_xdr() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(30);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    let bytes = vars.contract.export_lockup(&id);
+    let lockup = Lockup::from_xdr(&vars.env, &bytes).unwrap();
+
+    assert_eq!(lockup, vars.contract.get_lockup(&id));
+    assert_eq!(lockup.withdrawn, 300);
+}
+
+#[test]
+fn test_get_stream_should_return_the_default_rate_set_by_the_admin() {
+    let vars = SetupStreamTest::setup(1000);
+    let now = vars.env.ledger().timestamp();
+
+    vars.initialize();
+
+    assert_eq!(vars.contract.get_default_rate(), None);
+
+    vars.contract.set_default_rate(&Rate::Weekly);
+
+    assert_eq!(vars.contract.get_default_rate(), Some(Rate::Weekly));
+
+    let params = LockupInput {
+        end_date: now + 1000,
+        options: LockupOptions {
+            use_default_rate: true,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    let id = vars.contract.create_stream(&params);
+    let stream = vars.contract.get_lockup(&id);
+
+    assert_eq!(stream.rate, Rate::Weekly);
+
+    // The rate is only for display, the stream still accrues every second
+    vars.move_ledger_timestamp_to(now + 250);
+
+    assert_eq!(vars.contract.get_withdrawable(&id), 250);
+}
+
+#[test]
+fn test_default_rate_should_not_replace_the_rate_set_by_the_caller() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+    vars.contract.set_default_rate(&Rate::Weekly);
+
+    let params = LockupInput {
+        rate: Rate::Daily,
+        ..vars.lockup_input()
+    };
+
+    let id = vars.contract.create_stream(&params);
+
+    assert_eq!(vars.contract.get_lockup(&id).rate, Rate::Daily);
+    assert_eq!(vars.contract.get_lockup_input(&id).rate, Rate::Daily);
+}
+
+#[test]
+fn test_default_rate_should_not_apply_to_vestings() {
+    let (vars, _) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let now = vars.env.ledger().timestamp();
+
+    vars.initialize();
+    vars.contract.set_default_rate(&Rate::Weekly);
+
+    assert_eq!(
+        vars.contract.get_lockup(&1).rate,
+        Rate::Monthly,
+        "existing streams keep their rate"
+    );
+
+    let token_admin = soroban_sdk::token::StellarAssetClient::new(&vars.env, &vars.token.address);
+    token_admin.mint(&vars.admin, &1000);
+    vars.token
+        .approve(&vars.admin, &vars.contract.address, &1000, &6311000);
+
+    let params = LockupInput {
+        end_date: now + 10 * 86400,
+        rate: Rate::Daily,
+        options: LockupOptions::default(),
+        ..vars.lockup_input()
+    };
+
+    let id = vars.contract.create_vesting(&params);
+
+    assert_eq!(vars.contract.get_lockup(&id).rate, Rate::Daily);
+}
@@ -0,0 +1,288 @@
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{ExternalRef, LockupInput, LockupOptions},
+};
+
+use super::setup::SetupStreamTest;
+
+fn group_params(vars: &SetupStreamTest, amount: i128) -> LockupInput {
+    LockupInput {
+        receiver: vars.admin.clone(),
+        amount,
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        ..vars.lockup_input()
+    }
+}
+
+#[test]
+fn test_group_stream_should_be_withdrawn_independently_by_the_members() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let alice = Address::generate(&vars.env);
+    let bob = Address::generate(&vars.env);
+
+    let id = vars.contract.create_group_stream(
+        &group_params(&vars, 1000),
+        &vec![&vars.env, (alice.clone(), 2), (bob.clone(), 1)],
+    );
+
+    assert_eq!(vars.contract.get_latest_lockup_id(), id);
+    assert_eq!(vars.contract.get_lockup(&id).amount, 1000);
+    assert_eq!(vars.token.balance(&vars.contract.address), 1000);
+
+    vars.move_ledger_timestamp_to(30);
+
+    assert_eq!(vars.contract.withdraw_group_share(&id, &alice, &0), 200);
+    assert_eq!(vars.token.balance(&alice), 200);
+    assert_eq!(vars.token.balance(&bob), 0);
+
+    vars.move_ledger_timestamp_to(60);
+
+    assert_eq!(vars.contract.withdraw_group_share(&id, &bob, &0), 200);
+    assert_eq!(vars.token.balance(&alice), 200);
+    assert_eq!(vars.token.balance(&bob), 200);
+    assert_eq!(vars.contract.get_group_withdrawn(&id, &alice), 200);
+    assert_eq!(vars.contract.get_group_withdrawn(&id, &bob), 200);
+    assert_eq!(vars.contract.get_lockup(&id).withdrawn, 400);
+
+    vars.move_ledger_timestamp_to(100);
+    vars.contract.withdraw_group_share(&id, &alice, &0);
+    vars.contract.withdraw_group_share(&id, &bob, &0);
+
+    assert_eq!(vars.token.balance(&alice), 666);
+    assert_eq!(vars.token.balance(&bob), 334);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+    assert!(vars.contract.is_lockup_settled(&id));
+}
+
+#[test]
+fn test_group_stream_should_withdraw_a_specified_amount_of_a_share() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let alice = Address::generate(&vars.env);
+    let bob = Address::generate(&vars.env);
+
+    let id = vars.contract.create_group_stream(
+        &group_params(&vars, 1000),
+        &vec![&vars.env, (alice.clone(), 1), (bob.clone(), 1)],
+    );
+
+    vars.move_ledger_timestamp_to(40);
+
+    assert_eq!(vars.contract.withdraw_group_share(&id, &bob, &150), 150);
+
+    let result = vars.contract.try_withdraw_group_share(&id, &bob, &51);
+
+    assert_eq!(
+        result,
+        Err(Ok(CustomErrors::SpecifiedAmountIsGreaterThanWithdrawable))
+    );
+    assert_eq!(vars.contract.withdraw_group_share(&id, &alice, &0), 200);
+}
+
+#[test]
+fn test_group_stream_should_pull_the_deposit_once() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let external_ref = BytesN::from_array(&vars.env, &[7; 32]);
+    let params = LockupInput {
+        options: LockupOptions {
+            external_ref: ExternalRef::Some(external_ref.clone()),
+            ..Default::default()
+        },
+        ..group_params(&vars, 1000)
+    };
+
+    let id = vars.contract.create_group_stream(
+        &params,
+        &vec![
+            &vars.env,
+            (Address::generate(&vars.env), 1),
+            (Address::generate(&vars.env), 1),
+            (Address::generate(&vars.env), 1),
+        ],
+    );
+
+    assert_eq!(vars.token.balance(&vars.admin), 0);
+    assert_eq!(vars.token.balance(&vars.contract.address), 1000);
+    assert_eq!(vars.contract.get_lockup_by_external_ref(&external_ref), id);
+    assert_eq!(vars.contract.get_group_shares(&id).unwrap().len(), 3);
+}
+
+#[test]
+fn test_group_stream_should_leave_the_vested_shares_claimable_after_cancellation() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let alice = Address::generate(&vars.env);
+    let bob = Address::generate(&vars.env);
+
+    let id = vars.contract.create_group_stream(
+        &group_params(&vars, 1000),
+        &vec![&vars.env, (alice.clone(), 1), (bob.clone(), 3)],
+    );
+
+    vars.move_ledger_timestamp_to(40);
+
+    let result = vars.contract.try_cancel_lockup(&id, &true);
+
+    assert_eq!(result, Err(Ok(CustomErrors::UnsupportedForGroup)));
+    assert_eq!(vars.contract.cancel_lockup(&id, &false), (600, 0));
+
+    vars.move_ledger_timestamp_to(80);
+
+    assert_eq!(vars.contract.withdraw_group_share(&id, &alice, &0), 100);
+    assert_eq!(vars.contract.withdraw_group_share(&id, &bob, &0), 300);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+}
+
+#[test]
+fn test_group_stream_should_reject_paying_the_receiver() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let id = vars.contract.create_group_stream(
+        &group_params(&vars, 1000),
+        &vec![&vars.env, (Address::generate(&vars.env), 1)],
+    );
+
+    vars.move_ledger_timestamp_to(100);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::UnsupportedForGroup)));
+
+    let result = vars.contract.try_finalize_lockup(&id);
+
+    assert_eq!(result, Err(Ok(CustomErrors::UnsupportedForGroup)));
+}
+
+#[test]
+fn test_withdraw_group_share_should_revert_when_not_a_member() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let id = vars.contract.create_group_stream(
+        &group_params(&vars, 500),
+        &vec![&vars.env, (Address::generate(&vars.env), 1)],
+    );
+    let stream_id = vars.contract.create_stream(&LockupInput {
+        receiver: Address::generate(&vars.env),
+        ..group_params(&vars, 500)
+    });
+
+    vars.move_ledger_timestamp_to(50);
+
+    let stranger = Address::generate(&vars.env);
+
+    let result = vars.contract.try_withdraw_group_share(&id, &stranger, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::NotGroupMember)));
+
+    let result = vars
+        .contract
+        .try_withdraw_group_share(&stream_id, &stranger, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::NotGroupMember)));
+}
+
+#[test]
+fn test_withdraw_group_share_should_check_the_minimum_against_the_share() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+    vars.contract.set_min_withdraw(&100);
+
+    let alice = Address::generate(&vars.env);
+    let bob = Address::generate(&vars.env);
+
+    let id = vars.contract.create_group_stream(
+        &group_params(&vars, 1000),
+        &vec![&vars.env, (alice.clone(), 2), (bob.clone(), 1)],
+    );
+
+    // The group has 120 to withdraw, but alice's share of it is below the minimum
+    vars.move_ledger_timestamp_to(12);
+
+    let result = vars.contract.try_withdraw_group_share(&id, &alice, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::WithdrawBelowMinimum)));
+
+    vars.move_ledger_timestamp_to(30);
+
+    assert_eq!(vars.contract.withdraw_group_share(&id, &alice, &0), 200);
+
+    vars.move_ledger_timestamp_to(90);
+
+    assert_eq!(vars.contract.withdraw_group_share(&id, &bob, &0), 300);
+
+    // The last withdrawal of a share may be below the minimum
+    vars.move_ledger_timestamp_to(100);
+
+    assert_eq!(vars.contract.withdraw_group_share(&id, &bob, &0), 34);
+}
+
+#[test]
+fn test_group_stream_should_revert_without_shares() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let result = vars
+        .contract
+        .try_create_group_stream(&group_params(&vars, 1000), &vec![&vars.env]);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidAmount)));
+
+    let result = vars.contract.try_create_group_stream(
+        &group_params(&vars, 1000),
+        &vec![
+            &vars.env,
+            (Address::generate(&vars.env), 1),
+            (Address::generate(&vars.env), 0),
+        ],
+    );
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidAmount)));
+    assert_eq!(vars.contract.get_latest_lockup_id(), 0);
+}
+
+#[test]
+fn test_group_stream_should_revert_when_a_member_is_listed_twice() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let alice = Address::generate(&vars.env);
+
+    let result = vars.contract.try_create_group_stream(
+        &group_params(&vars, 1000),
+        &vec![&vars.env, (alice.clone(), 1), (alice, 2)],
+    );
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidReceiver)));
+
+    let result = vars.contract.try_create_group_stream(
+        &group_params(&vars, 1000),
+        &vec![&vars.env, (vars.admin.clone(), 1)],
+    );
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidReceiver)));
+}
+
+#[test]
+fn test_group_stream_should_revert_when_tokenized() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = LockupInput {
+        options: LockupOptions {
+            tokenized: true,
+            ..Default::default()
+        },
+        ..group_params(&vars, 1000)
+    };
+
+    let result = vars
+        .contract
+        .try_create_group_stream(&params, &vec![&vars.env, (Address::generate(&vars.env), 1)]);
+
+    assert_eq!(result, Err(Ok(CustomErrors::UnsupportedForGroup)));
+}
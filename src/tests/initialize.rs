@@ -0,0 +1,138 @@
+use soroban_sdk::{testutils::Address as _, Address};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{ContractConfig, FeeConfig, FeeStrategy, FeeToken, LockupInput},
+};
+
+use super::setup::SetupStreamTest;
+
+fn stream_params(vars: &SetupStreamTest, end_date: u64) -> LockupInput {
+    LockupInput {
+        amount: 500,
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date,
+        ..vars.lockup_input()
+    }
+}
+
+#[test]
+fn test_initialize_should_apply_the_config() {
+    let vars = SetupStreamTest::setup(1000);
+    let fee_recipient = Address::generate(&vars.env);
+
+    vars.contract.initialize(
+        &vars.admin,
+        &ContractConfig {
+            fee_bps: 100,
+            fee_recipient: fee_recipient.clone(),
+            min_duration: 50,
+        },
+    );
+
+    assert_eq!(
+        vars.contract.get_fee_config(),
+        Some(FeeConfig {
+            fee_bps: 100,
+            fee_recipient,
+            fee_token: FeeToken::None,
+            cancel_fee_bps: 0,
+            fee_strategy: FeeStrategy::None,
+        })
+    );
+    assert_eq!(vars.contract.get_min_duration(), 50);
+
+    let result = vars.contract.try_create_stream(&stream_params(&vars, 49));
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidDuration)));
+
+    vars.contract.create_stream(&stream_params(&vars, 50));
+}
+
+#[test]
+fn test_initialize_should_revert_when_called_twice() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+
+    let result = vars
+        .contract
+        .try_initialize(&vars.admin, &vars.default_config());
+
+    assert_eq!(result, Err(Ok(CustomErrors::AlreadyInitialized)));
+}
+
+#[test]
+fn test_initialize_should_revert_when_fee_bps_is_too_high() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let result = vars.contract.try_initialize(
+        &vars.admin,
+        &ContractConfig {
+            fee_bps: 10001,
+            fee_recipient: vars.admin.clone(),
+            min_duration: 0,
+        },
+    );
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidFeeConfig)));
+
+    vars.initialize();
+}
+
+#[test]
+fn test_max_duration_should_reject_longer_lockups() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+
+    assert_eq!(vars.contract.get_max_duration(), None);
+
+    vars.contract.set_max_duration(&100);
+
+    assert_eq!(vars.contract.get_max_duration(), Some(100));
+
+    let result = vars.contract.try_create_stream(&stream_params(&vars, 101));
+
+    assert_eq!(result, Err(Ok(CustomErrors::DurationTooLong)));
+
+    vars.contract.create_stream(&stream_params(&vars, 100));
+}
+
+#[test]
+fn test_set_max_duration_should_revert_below_the_min_duration() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.contract.initialize(
+        &vars.admin,
+        &ContractConfig {
+            min_duration: 50,
+            ..vars.default_config()
+        },
+    );
+
+    let result = vars.contract.try_set_max_duration(&49);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidDuration)));
+    assert_eq!(vars.contract.get_max_duration(), None);
+
+    vars.contract.set_max_duration(&50);
+}
+
+#[test]
+fn test_initialize_should_be_authorized_by_the_admin() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+
+    let authorizers: super::std::vec::Vec<Address> = vars
+        .env
+        .auths()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+
+    assert_eq!(authorizers, [vars.admin]);
+}
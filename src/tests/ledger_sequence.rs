@@ -0,0 +1,177 @@
+use soroban_sdk::testutils::Ledger;
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{ContractConfig, LockupInput, LockupOptions, LockupStatus},
+};
+
+use super::setup::SetupStreamTest;
+
+fn ledger_seq_params(vars: &SetupStreamTest, start: u64, end: u64) -> LockupInput {
+    LockupInput {
+        cancellable_date: start,
+        cliff_date: start,
+        start_date: start,
+        end_date: end,
+        options: LockupOptions {
+            use_ledger_seq: true,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    }
+}
+
+#[test]
+fn test_ledger_seq_stream_should_accrue_with_the_ledger_sequence() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.env.ledger().with_mut(|l| l.sequence_number = 1000);
+
+    let id = vars
+        .contract
+        .create_stream(&ledger_seq_params(&vars, 1000, 1100));
+
+    // The timestamp moving alone accrues nothing
+    vars.move_ledger_timestamp_to(1_000_000);
+
+    assert_eq!(vars.contract.get_withdrawable(&id), 0);
+    assert_eq!(
+        vars.contract.get_lockup_status(&id),
+        LockupStatus::NotStarted
+    );
+
+    vars.env.ledger().with_mut(|l| l.sequence_number = 1025);
+
+    assert_eq!(vars.contract.get_withdrawable(&id), 250);
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 250);
+
+    vars.env.ledger().with_mut(|l| l.sequence_number = 1060);
+    vars.contract.cancel_lockup(&id, &false);
+
+    assert_eq!(vars.contract.get_lockup(&id).cancelled_date, 1060);
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 350);
+
+    vars.env.ledger().with_mut(|l| l.sequence_number = 1200);
+
+    assert_eq!(vars.contract.get_withdrawable(&id), 0);
+    assert!(vars.contract.is_lockup_settled(&id));
+}
+
+#[test]
+fn test_ledger_seq_should_be_rejected_for_vestings() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let result = vars
+        .contract
+        .try_create_vesting(&ledger_seq_params(&vars, 0, 90 * 86400));
+
+    assert_eq!(result, Err(Ok(CustomErrors::UnsupportedForVesting)));
+}
+
+#[test]
+fn test_ledger_seq_duration_should_be_checked_in_seconds() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.contract.initialize(
+        &vars.admin,
+        &ContractConfig {
+            min_duration: 500,
+            ..vars.default_config()
+        },
+    );
+
+    let result = vars
+        .contract
+        .try_create_stream(&ledger_seq_params(&vars, 0, 99));
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidDuration)));
+
+    vars.contract
+        .create_stream(&ledger_seq_params(&vars, 0, 100));
+}
+
+#[test]
+fn test_ledger_seq_time_since_cancel_should_be_in_seconds() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let id = vars
+        .contract
+        .create_stream(&ledger_seq_params(&vars, 0, 100));
+
+    vars.env.ledger().with_mut(|l| l.sequence_number = 60);
+    vars.contract.cancel_lockup(&id, &true);
+
+    vars.env.ledger().with_mut(|l| l.sequence_number = 70);
+
+    assert_eq!(vars.contract.get_time_since_cancel(&id), 50);
+}
+
+#[test]
+fn test_ledger_seq_dormancy_should_be_measured_in_seconds() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+    vars.contract.set_dormancy_period(&1000);
+
+    let id = vars
+        .contract
+        .create_stream(&ledger_seq_params(&vars, 0, 100));
+
+    vars.env.ledger().with_mut(|l| l.sequence_number = 300);
+
+    let result = vars.contract.try_reclaim_dormant(&id);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupNotDormant)));
+
+    vars.env.ledger().with_mut(|l| l.sequence_number = 301);
+
+    assert_eq!(vars.contract.reclaim_dormant(&id), 1000);
+}
+
+#[test]
+fn test_ledger_seq_max_duration_should_be_checked_in_seconds() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+    vars.contract.set_max_duration(&500);
+
+    let result = vars
+        .contract
+        .try_create_stream(&ledger_seq_params(&vars, 0, 101));
+
+    assert_eq!(result, Err(Ok(CustomErrors::DurationTooLong)));
+
+    vars.contract
+        .create_stream(&ledger_seq_params(&vars, 0, 100));
+}
+
+#[test]
+fn test_ledger_seq_rate_should_be_per_ledger() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.env.ledger().with_mut(|l| l.sequence_number = 1000);
+
+    let id = vars
+        .contract
+        .create_stream(&ledger_seq_params(&vars, 1000, 1100));
+
+    let rate = vars.contract.get_lockup_rate(&id);
+
+    assert_eq!(rate, 10);
+
+    vars.env.ledger().with_mut(|l| l.sequence_number = 1020);
+
+    assert_eq!(vars.contract.get_next_unlock(&id), (1020, rate));
+    assert_eq!(vars.contract.get_withdrawable(&id), rate * 20);
+}
+
+#[test]
+fn test_ledger_seq_should_be_rejected_for_streams_by_rate() {
+    let vars = SetupStreamTest::setup(3000);
+
+    let result = vars
+        .contract
+        .try_create_stream_by_rate(&ledger_seq_params(&vars, 0, 30 * 86400), &100);
+
+    assert_eq!(result, Err(Ok(CustomErrors::UnsupportedForLedgerSeq)));
+}
@@ -0,0 +1,342 @@
+use soroban_sdk::vec;
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{CliffMode, LockupInput, LockupOptions, LockupStatus, Rate, StreamMode},
+};
+
+use super::setup::{SetupStreamTest, StreamFields, VestingFields};
+
+#[test]
+fn test_get_lockup_detail_should_match_the_individual_views() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        start_date: 10,
+        end_date: 110,
+        cliff_date: 10,
+        ..Default::default()
+    });
+
+    for timestamp in [0, 10, 35, 60, 110, 200] {
+        vars.move_ledger_timestamp_to(timestamp);
+
+        let detail = vars.contract.get_lockup_detail(&id);
+
+        assert_eq!(detail.lockup, vars.contract.get_lockup(&id));
+        assert_eq!(detail.status, vars.contract.get_lockup_status(&id));
+        assert_eq!(detail.withdrawable, vars.contract.get_withdrawable(&id));
+        assert_eq!(detail.vested, detail.withdrawable + detail.lockup.withdrawn);
+    }
+}
+
+#[test]
+fn test_get_lockup_detail_should_compute_fields_during_the_stream() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(25);
+    vars.contract.withdraw_lockup(&id, &100);
+
+    vars.move_ledger_timestamp_to(40);
+
+    let detail = vars.contract.get_lockup_detail(&id);
+
+    assert_eq!(detail.status, LockupStatus::Active);
+    assert_eq!(detail.vested, 400);
+    assert_eq!(detail.withdrawable, 300);
+    assert_eq!(detail.progress_bps, 4000);
+}
+
+#[test]
+fn test_get_lockup_status_should_follow_the_lifecycle() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        start_date: 10,
+        end_date: 110,
+        cliff_date: 10,
+        ..Default::default()
+    });
+
+    assert_eq!(
+        vars.contract.get_lockup_status(&id),
+        LockupStatus::NotStarted
+    );
+
+    vars.move_ledger_timestamp_to(50);
+    assert_eq!(vars.contract.get_lockup_status(&id), LockupStatus::Active);
+
+    vars.move_ledger_timestamp_to(110);
+    assert_eq!(vars.contract.get_lockup_status(&id), LockupStatus::Ended);
+}
+
+#[test]
+fn test_get_lockup_detail_should_freeze_at_cancellation() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(30);
+    vars.contract.cancel_lockup(&id, &true);
+
+    vars.move_ledger_timestamp_to(80);
+
+    let detail = vars.contract.get_lockup_detail(&id);
+
+    assert_eq!(detail.status, LockupStatus::Cancelled);
+    assert_eq!(detail.vested, 300);
+    assert_eq!(detail.withdrawable, 0);
+    assert_eq!(detail.progress_bps, 3000);
+}
+
+#[test]
+fn test_get_vested_at_should_project_linear_streams() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        start_date: 10,
+        end_date: 110,
+        cliff_date: 10,
+        ..Default::default()
+    });
+
+    assert_eq!(vars.contract.get_vested_at(&id, &0), 0);
+    assert_eq!(vars.contract.get_vested_at(&id, &10), 0);
+    assert_eq!(vars.contract.get_vested_at(&id, &35), 250);
+    assert_eq!(vars.contract.get_vested_at(&id, &60), 500);
+    assert_eq!(vars.contract.get_vested_at(&id, &110), 1000);
+    assert_eq!(vars.contract.get_vested_at(&id, &10_000), 1000);
+}
+
+#[test]
+fn test_get_vested_at_should_project_vestings() {
+    let (vars, id) = SetupStreamTest::setup_with_vesting_created(VestingFields {
+        end_date: 4 * 86400,
+        ..Default::default()
+    });
+
+    assert_eq!(vars.contract.get_vested_at(&id, &0), 0);
+    assert_eq!(vars.contract.get_vested_at(&id, &86399), 0);
+    assert_eq!(vars.contract.get_vested_at(&id, &86400), 250);
+    assert_eq!(vars.contract.get_vested_at(&id, &(3 * 86400 + 10)), 750);
+    assert_eq!(vars.contract.get_vested_at(&id, &(10 * 86400)), 1000);
+}
+
+#[test]
+fn test_get_vested_at_should_stop_at_the_cancellation_date() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(40);
+    vars.contract.cancel_lockup(&id, &false);
+
+    assert_eq!(vars.contract.get_vested_at(&id, &20), 200);
+    assert_eq!(vars.contract.get_vested_at(&id, &100), 400);
+}
+
+#[test]
+fn test_get_next_unlock_should_return_the_next_period_of_monthly_vestings() {
+    let (vars, id) = SetupStreamTest::setup_with_vesting_created(VestingFields {
+        amount: 1200,
+        rate: Rate::Monthly,
+        end_date: 12 * 2592000,
+        ..Default::default()
+    });
+
+    assert_eq!(vars.contract.get_next_unlock(&id), (2592000, 100));
+
+    vars.move_ledger_timestamp_to(2592000 + 10);
+    assert_eq!(vars.contract.get_next_unlock(&id), (2 * 2592000, 100));
+
+    vars.move_ledger_timestamp_to(11 * 2592000);
+    assert_eq!(vars.contract.get_next_unlock(&id), (12 * 2592000, 100));
+
+    vars.move_ledger_timestamp_to(12 * 2592000);
+    assert_eq!(vars.contract.get_next_unlock(&id), (12 * 2592000, 0));
+}
+
+#[test]
+fn test_get_next_unlock_should_return_now_and_the_rate_for_streams() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(30);
+
+    assert_eq!(vars.contract.get_next_unlock(&id), (30, 10));
+}
+
+#[test]
+fn test_get_lockup_rate_should_match_the_observed_accrual() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let rate = vars.contract.get_lockup_rate(&id);
+
+    assert_eq!(rate, 10);
+
+    vars.move_ledger_timestamp_to(20);
+    let before = vars.contract.get_withdrawable(&id);
+
+    vars.move_ledger_timestamp_to(50);
+    let after = vars.contract.get_withdrawable(&id);
+
+    assert_eq!(after - before, rate * 30);
+}
+
+#[test]
+fn test_get_lockup_rate_should_average_vestings() {
+    let (vars, id) = SetupStreamTest::setup_with_vesting_created(VestingFields {
+        amount: 864000,
+        end_date: 10 * 86400,
+        ..Default::default()
+    });
+
+    let rate = vars.contract.get_lockup_rate(&id);
+
+    assert_eq!(rate, 1);
+    assert_eq!(
+        vars.contract.get_vested_at(&id, &(4 * 86400)),
+        rate * 4 * 86400
+    );
+}
+
+#[test]
+fn test_get_lockup_rate_should_spread_linear_from_cliff_streams_from_the_cliff() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = LockupInput {
+        cliff_date: 50,
+        options: LockupOptions {
+            cliff_mode: CliffMode::LinearFromCliff,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    let id = vars.contract.create_stream(&params);
+
+    assert_eq!(vars.contract.get_lockup_rate(&id), 20);
+
+    vars.move_ledger_timestamp_to(60);
+    assert_eq!(vars.contract.get_next_unlock(&id), (60, 20));
+
+    let before = vars.contract.get_withdrawable(&id);
+    vars.move_ledger_timestamp_to(70);
+
+    assert_eq!(vars.contract.get_withdrawable(&id) - before, 20 * 10);
+}
+
+#[test]
+fn test_get_lockup_rate_should_be_negative_for_reversed_streams() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = LockupInput {
+        options: LockupOptions {
+            mode: StreamMode::Reverse,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    let id = vars.contract.create_stream(&params);
+
+    assert_eq!(vars.contract.get_lockup_rate(&id), -10);
+
+    vars.move_ledger_timestamp_to(30);
+    assert_eq!(vars.contract.get_next_unlock(&id), (30, 0));
+
+    let before = vars.contract.get_withdrawable(&id);
+    vars.move_ledger_timestamp_to(40);
+
+    assert_eq!(vars.contract.get_withdrawable(&id) - before, -10 * 10);
+}
+
+#[test]
+fn test_get_lockup_rate_should_revert_when_duration_is_zero() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let mut stream = vars.contract.get_lockup(&id);
+    stream.end_date = stream.start_date;
+    stream.cliff_date = stream.start_date;
+    vars.set_lockup(id, &stream);
+
+    let result = vars.contract.try_get_lockup_rate(&id);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidDuration)));
+}
+
+#[test]
+fn test_list_lockups_detail_should_page_through_the_lockups() {
+    let vars = SetupStreamTest::setup(12000);
+
+    let params = LockupInput {
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        ..vars.lockup_input()
+    };
+
+    for _ in 0..12 {
+        vars.contract.create_stream(&params);
+    }
+
+    vars.move_ledger_timestamp_to(40);
+
+    let (details, cursor) = vars.contract.list_lockups_detail(&0, &50);
+
+    assert_eq!(details.len(), 10);
+    assert_eq!(cursor, 11);
+
+    for detail in details.iter() {
+        assert_eq!(detail.status, LockupStatus::Active);
+        assert_eq!(detail.vested, 400);
+        assert_eq!(detail.withdrawable, 400);
+        assert_eq!(detail.progress_bps, 4000);
+    }
+
+    vars.contract.withdraw_lockup(&12, &100);
+
+    let (details, cursor) = vars.contract.list_lockups_detail(&cursor, &50);
+
+    assert_eq!(details.len(), 2);
+    assert_eq!(details.get_unchecked(1).withdrawable, 300);
+    assert_eq!(cursor, 0);
+}
+
+#[test]
+fn test_list_lockups_by_status_should_filter_cancelled_and_active_lockups() {
+    let vars = SetupStreamTest::setup(6000);
+
+    let params = LockupInput {
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        ..vars.lockup_input()
+    };
+
+    for _ in 0..6 {
+        vars.contract.create_stream(&params);
+    }
+
+    vars.move_ledger_timestamp_to(40);
+    vars.contract.cancel_lockup(&2, &false);
+    vars.contract.cancel_lockup(&5, &false);
+
+    let (ids, cursor) = vars
+        .contract
+        .list_lockups_by_status(&LockupStatus::Cancelled, &0, &50);
+
+    assert_eq!(ids, vec![&vars.env, 2, 5]);
+    assert_eq!(cursor, 0);
+
+    let (ids, cursor) = vars
+        .contract
+        .list_lockups_by_status(&LockupStatus::Active, &0, &2);
+
+    assert_eq!(ids, vec![&vars.env, 1, 3]);
+    assert_eq!(cursor, 4);
+
+    let (ids, cursor) = vars
+        .contract
+        .list_lockups_by_status(&LockupStatus::Active, &cursor, &2);
+
+    assert_eq!(ids, vec![&vars.env, 4, 6]);
+    assert_eq!(cursor, 0);
+
+    let (ids, _) = vars
+        .contract
+        .list_lockups_by_status(&LockupStatus::Ended, &0, &50);
+
+    assert!(ids.is_empty());
+}
@@ -0,0 +1,96 @@
+use soroban_sdk::{testutils::Address as _, vec, Address};
+
+use crate::base::types::LockupInput;
+
+use super::setup::SetupStreamTest;
+
+fn create_stream(vars: &SetupStreamTest, receiver: &Address) -> u64 {
+    let params = LockupInput {
+        receiver: receiver.clone(),
+        amount: 100,
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_stream(&params)
+}
+
+#[test]
+fn test_get_lockups_between_parties_should_filter_by_receiver() {
+    let vars = SetupStreamTest::setup(500);
+    let receiver = Address::generate(&vars.env);
+    let other_receiver = Address::generate(&vars.env);
+
+    let id0 = create_stream(&vars, &receiver);
+    create_stream(&vars, &other_receiver);
+    let id2 = create_stream(&vars, &receiver);
+    create_stream(&vars, &other_receiver);
+    let id4 = create_stream(&vars, &receiver);
+
+    assert_eq!(
+        vars.contract
+            .get_lockups_between_parties(&vars.admin, &receiver, &0, &10)
+            .0,
+        vec![&vars.env, id0, id2, id4]
+    );
+    assert_eq!(
+        vars.contract
+            .get_lockups_between_parties(&vars.admin, &receiver, &0, &2)
+            .0,
+        vec![&vars.env, id0, id2]
+    );
+}
+
+#[test]
+fn test_get_lockups_between_parties_should_be_empty_for_unrelated_parties() {
+    let vars = SetupStreamTest::setup(100);
+    let receiver = Address::generate(&vars.env);
+
+    create_stream(&vars, &receiver);
+
+    let other_sender = Address::generate(&vars.env);
+
+    assert!(vars
+        .contract
+        .get_lockups_between_parties(&other_sender, &receiver, &0, &10)
+        .0
+        .is_empty());
+    assert!(vars
+        .contract
+        .get_lockups_between_parties(&vars.admin, &other_sender, &0, &10)
+        .0
+        .is_empty());
+}
+
+#[test]
+fn test_get_lockups_between_parties_should_page_to_newer_lockups() {
+    let vars = SetupStreamTest::setup(12100);
+    let receiver = Address::generate(&vars.env);
+    let other_receiver = Address::generate(&vars.env);
+
+    // The budget of the test covers every call it makes
+    vars.env.budget().reset_unlimited();
+
+    for _ in 0..120 {
+        create_stream(&vars, &other_receiver);
+    }
+
+    let id = create_stream(&vars, &receiver);
+
+    let (ids, cursor) = vars
+        .contract
+        .get_lockups_between_parties(&vars.admin, &receiver, &0, &10);
+
+    assert!(ids.is_empty());
+    assert_eq!(cursor, 100);
+
+    let (ids, cursor) =
+        vars.contract
+            .get_lockups_between_parties(&vars.admin, &receiver, &cursor, &10);
+
+    assert_eq!(ids, vec![&vars.env, id]);
+    assert_eq!(cursor, 0);
+}
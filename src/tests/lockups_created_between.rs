@@ -0,0 +1,79 @@
+use soroban_sdk::vec;
+
+use crate::base::{data_key::DataKey, types::LockupInput};
+
+use super::setup::SetupStreamTest;
+
+fn create_stream_at(vars: &SetupStreamTest, timestamp: u64) -> u64 {
+    vars.move_ledger_timestamp_to(timestamp);
+
+    let params = LockupInput {
+        amount: 100,
+        cancellable_date: timestamp,
+        cliff_date: timestamp,
+        start_date: timestamp,
+        end_date: timestamp + 100,
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_stream(&params)
+}
+
+#[test]
+fn test_created_at_should_be_stored_on_create() {
+    let vars = SetupStreamTest::setup(100);
+
+    let id = create_stream_at(&vars, 500);
+
+    assert_eq!(vars.contract.get_lockup(&id).created_at, 500);
+}
+
+#[test]
+fn test_get_lockups_created_between_should_filter_a_sub_window() {
+    let vars = SetupStreamTest::setup(500);
+
+    for timestamp in [100, 200, 300, 300, 400] {
+        create_stream_at(&vars, timestamp);
+    }
+
+    assert_eq!(
+        vars.contract.get_lockups_created_between(&200, &300, &10),
+        vec![&vars.env, 2, 3, 4]
+    );
+    assert_eq!(
+        vars.contract.get_lockups_created_between(&150, &350, &2),
+        vec![&vars.env, 2, 3]
+    );
+    assert_eq!(
+        vars.contract.get_lockups_created_between(&401, &1000, &10),
+        vec![&vars.env]
+    );
+}
+
+#[test]
+fn test_get_lockups_created_between_should_return_nothing_without_lockups() {
+    let vars = SetupStreamTest::setup(500);
+
+    assert_eq!(
+        vars.contract.get_lockups_created_between(&0, &1000, &10),
+        vec![&vars.env]
+    );
+}
+
+#[test]
+fn test_get_lockups_created_between_should_skip_earlier_lockups_around_a_missing_id() {
+    let vars = SetupStreamTest::setup(500);
+
+    for timestamp in [100, 200, 300, 400, 500] {
+        create_stream_at(&vars, timestamp);
+    }
+
+    vars.env.as_contract(&vars.contract.address, || {
+        vars.env.storage().persistent().remove(&DataKey::Lockup(3));
+    });
+
+    assert_eq!(
+        vars.contract.get_lockups_created_between(&450, &1000, &10),
+        vec![&vars.env, 5]
+    );
+}
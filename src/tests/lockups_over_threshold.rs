@@ -0,0 +1,164 @@
+use soroban_sdk::{testutils::Address as _, vec, Address};
+
+use crate::base::{
+    data_key::{DataKey, LockupIndex},
+    types::LockupInput,
+};
+
+use super::setup::SetupStreamTest;
+
+fn create_stream(vars: &SetupStreamTest, receiver: &Address, amount: i128) -> u64 {
+    let params = LockupInput {
+        receiver: receiver.clone(),
+        amount,
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_stream(&params)
+}
+
+#[test]
+fn test_get_lockups_over_threshold_should_filter_by_withdrawable() {
+    let vars = SetupStreamTest::setup(3000);
+
+    let receiver = Address::generate(&vars.env);
+    let other = Address::generate(&vars.env);
+
+    let small = create_stream(&vars, &receiver, 100);
+    let large = create_stream(&vars, &receiver, 1900);
+    create_stream(&vars, &other, 1000);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let ids = vars
+        .contract
+        .get_lockups_over_threshold(&receiver, &100, &0, &10)
+        .0;
+    assert_eq!(ids, vec![&vars.env, large]);
+
+    let ids = vars
+        .contract
+        .get_lockups_over_threshold(&receiver, &50, &0, &10)
+        .0;
+    assert_eq!(ids, vec![&vars.env, small, large]);
+
+    let ids = vars
+        .contract
+        .get_lockups_over_threshold(&receiver, &1000, &0, &10)
+        .0;
+    assert_eq!(ids, vec![&vars.env]);
+}
+
+#[test]
+fn test_get_lockups_over_threshold_should_account_for_withdrawn_amounts() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let receiver = Address::generate(&vars.env);
+
+    let id = create_stream(&vars, &receiver, 2000);
+
+    vars.move_ledger_timestamp_to(50);
+
+    assert_eq!(
+        vars.contract
+            .get_lockups_over_threshold(&receiver, &500, &0, &10)
+            .0,
+        vec![&vars.env, id]
+    );
+
+    vars.contract.withdraw_lockup(&id, &600);
+
+    assert_eq!(
+        vars.contract
+            .get_lockups_over_threshold(&receiver, &500, &0, &10)
+            .0,
+        vec![&vars.env]
+    );
+}
+
+#[test]
+fn test_get_lockups_over_threshold_should_skip_lockups_before_start() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let receiver = Address::generate(&vars.env);
+
+    create_stream(&vars, &receiver, 2000);
+
+    assert_eq!(
+        vars.contract
+            .get_lockups_over_threshold(&receiver, &0, &0, &10)
+            .0,
+        vec![&vars.env]
+    );
+}
+
+#[test]
+fn test_get_lockups_over_threshold_should_page_past_the_first_chunk() {
+    let vars = SetupStreamTest::setup(120);
+
+    let receiver = Address::generate(&vars.env);
+    let mut expected = vec![&vars.env];
+
+    // The budget of the test covers every call it makes
+    vars.env.budget().reset_unlimited();
+
+    for _ in 0..120 {
+        expected.push_back(create_stream(&vars, &receiver, 1));
+    }
+
+    vars.move_ledger_timestamp_to(100);
+
+    let (first, cursor) = vars
+        .contract
+        .get_lockups_over_threshold(&receiver, &1, &0, &70);
+
+    assert_eq!(first, expected.slice(0..70));
+    assert_eq!(cursor, 70);
+
+    let (second, cursor) = vars
+        .contract
+        .get_lockups_over_threshold(&receiver, &1, &cursor, &70);
+
+    assert_eq!(second, expected.slice(70..120));
+    assert_eq!(cursor, 0);
+}
+
+#[test]
+fn test_get_lockups_over_threshold_should_read_a_legacy_index() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let receiver = Address::generate(&vars.env);
+    let first = create_stream(&vars, &receiver, 1000);
+    let second = create_stream(&vars, &receiver, 1000);
+
+    vars.env.as_contract(&vars.contract.address, || {
+        let storage = vars.env.storage().persistent();
+        let index = LockupIndex::Receiver(receiver.clone());
+
+        storage.remove(&DataKey::LockupIndexLen(index.clone()));
+        storage.remove(&DataKey::LockupIndexChunk(index, 0));
+        storage.set(
+            &DataKey::ReceiverLockups(receiver.clone()),
+            &vec![&vars.env, first, second],
+        );
+    });
+
+    vars.move_ledger_timestamp_to(50);
+
+    assert_eq!(
+        vars.contract
+            .get_lockups_over_threshold(&receiver, &0, &0, &10)
+            .0,
+        vec![&vars.env, first, second]
+    );
+
+    vars.contract
+        .transfer_lockup_receiver(&first, &Address::generate(&vars.env));
+
+    assert_eq!(
+        vars.contract
+            .get_lockups_over_threshold(&receiver, &0, &0, &10)
+            .0,
+        vec![&vars.env, second]
+    );
+}
@@ -0,0 +1,195 @@
+use soroban_sdk::{
+    testutils::Address as _, token::StellarAssetClient, vec, Address, TryFromVal, Val,
+};
+
+use crate::base::{
+    data_key::DataKey,
+    errors::CustomErrors,
+    types::{LockupInput, LockupStatus, LockupV1, Position, Rate, StoredLockup},
+};
+
+use super::setup::SetupStreamTest;
+
+fn store_v1_lockup(vars: &SetupStreamTest, id: u64) -> LockupV1 {
+    let lockup = LockupV1 {
+        withdrawn: 0,
+        is_cancelled: false,
+        sender: vars.admin.clone(),
+        receiver: Address::generate(&vars.env),
+        token: vars.token.address.clone(),
+        amount: 1000,
+        cancellable_date: 0,
+        cancelled_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        rate: Rate::Monthly,
+        is_vesting: false,
+    };
+
+    vars.env.as_contract(&vars.contract.address, || {
+        vars.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Lockup(id), &lockup);
+    });
+
+    lockup
+}
+
+fn stored_layout_is_current(vars: &SetupStreamTest, id: u64) -> bool {
+    vars.env.as_contract(&vars.contract.address, || {
+        let value: Val = vars
+            .env
+            .storage()
+            .persistent()
+            .get(&DataKey::Lockup(id))
+            .unwrap();
+
+        matches!(
+            StoredLockup::try_from_val(&vars.env, &value),
+            Ok(StoredLockup::V2(_))
+        )
+    })
+}
+
+#[test]
+fn test_v1_lockup_should_be_upgraded_on_read() {
+    let vars = SetupStreamTest::setup(1000);
+    let old = store_v1_lockup(&vars, 1);
+
+    let lockup = vars.contract.get_lockup(&1);
+
+    assert_eq!(lockup.receiver, old.receiver);
+    assert_eq!(lockup.amount, 1000);
+    assert_eq!(lockup.funded_amount, 1000);
+    assert_eq!(lockup.position, Position::None);
+    assert!(lockup.tranches.is_empty());
+    assert!(!stored_layout_is_current(&vars, 1));
+}
+
+#[test]
+fn test_migrate_lockup_should_rewrite_the_entry_in_the_current_layout() {
+    let vars = SetupStreamTest::setup(1000);
+    store_v1_lockup(&vars, 1);
+
+    let lockup = vars.contract.get_lockup(&1);
+
+    vars.contract.migrate_lockup(&1);
+
+    assert!(stored_layout_is_current(&vars, 1));
+    assert_eq!(vars.contract.get_lockup(&1), lockup);
+}
+
+#[test]
+fn test_migrate_lockup_should_count_the_legacy_lockup_in_the_locked_balance() {
+    let vars = SetupStreamTest::setup(1000);
+    store_v1_lockup(&vars, 1);
+
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 0);
+
+    vars.contract.migrate_lockup(&1);
+    vars.contract.migrate_lockup(&1);
+
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 1000);
+}
+
+#[test]
+fn test_withdraw_from_legacy_lockup_should_keep_its_remainder_locked() {
+    let vars = SetupStreamTest::setup(1000);
+    store_v1_lockup(&vars, 1);
+
+    StellarAssetClient::new(&vars.env, &vars.token.address).mint(&vars.contract.address, &1300);
+
+    vars.initialize();
+    vars.move_ledger_timestamp_to(40);
+    vars.contract.withdraw_lockup(&1, &0);
+
+    let to = Address::generate(&vars.env);
+
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 600);
+    assert_eq!(vars.contract.sweep_token(&vars.token.address, &to), 300);
+    assert_eq!(vars.token.balance(&vars.contract.address), 600);
+}
+
+#[test]
+fn test_sweep_token_should_sweep_nothing_before_the_legacy_lockups_are_migrated() {
+    let vars = SetupStreamTest::setup(1000);
+    store_v1_lockup(&vars, 0);
+    store_v1_lockup(&vars, 1);
+
+    vars.env.as_contract(&vars.contract.address, || {
+        vars.env
+            .storage()
+            .instance()
+            .set(&DataKey::LatestLockupId, &2_u64);
+    });
+
+    StellarAssetClient::new(&vars.env, &vars.token.address).mint(&vars.contract.address, &2300);
+
+    vars.initialize();
+
+    let to = Address::generate(&vars.env);
+
+    assert_eq!(vars.contract.sweep_token(&vars.token.address, &to), 0);
+    assert_eq!(vars.contract.migrate_lockups(&1), 1);
+    assert_eq!(vars.contract.sweep_token(&vars.token.address, &to), 0);
+    assert_eq!(vars.contract.migrate_lockups(&20), 0);
+    assert!(stored_layout_is_current(&vars, 0));
+    assert!(stored_layout_is_current(&vars, 1));
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 2000);
+    assert_eq!(vars.contract.sweep_token(&vars.token.address, &to), 300);
+    assert_eq!(vars.contract.migrate_lockups(&20), 0);
+}
+
+#[test]
+fn test_legacy_latest_lockup_id_should_be_read_as_the_next_id() {
+    let vars = SetupStreamTest::setup(1000);
+    store_v1_lockup(&vars, 0);
+    store_v1_lockup(&vars, 1);
+
+    vars.env.as_contract(&vars.contract.address, || {
+        vars.env
+            .storage()
+            .instance()
+            .set(&DataKey::LatestLockupId, &2_u64);
+    });
+
+    assert_eq!(vars.contract.get_latest_lockup_id(), 1);
+
+    let id = vars.contract.create_stream(&LockupInput {
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        ..vars.lockup_input()
+    });
+
+    assert_eq!(id, 2);
+    assert_eq!(vars.contract.get_latest_lockup_id(), 2);
+
+    vars.move_ledger_timestamp_to(10);
+
+    let (ids, cursor) = vars
+        .contract
+        .list_lockups_by_status(&LockupStatus::Active, &0, &10);
+
+    assert_eq!(ids, vec![&vars.env, 0, 1, 2]);
+    assert_eq!(cursor, 0);
+}
+
+#[test]
+fn test_get_lockup_should_revert_when_the_entry_can_not_be_decoded() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.env.as_contract(&vars.contract.address, || {
+        vars.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Lockup(1), &7_u32);
+    });
+
+    let result = vars.contract.try_get_lockup(&1);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupUndecodable)));
+}
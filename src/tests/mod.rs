@@ -1,9 +1,44 @@
 pub extern crate std;
 
+mod accelerate_lockup;
+mod bump_ttl;
+mod cancel_lockups;
 mod cancel_stream;
+mod cancellable_lockups;
+mod change_token;
 mod combined;
+mod conditional_lockup;
+mod config_events;
 mod create_stream;
 mod create_vesting;
+mod default_destination;
+mod epoch_alignment;
+mod external_ref;
+mod features;
+mod fees;
+mod finalize;
+mod fund_lockup;
 mod get_stream;
+mod group_stream;
+mod initialize;
+mod ledger_sequence;
+mod lockup_detail;
+mod lockups_between_parties;
+mod lockups_created_between;
+mod lockups_over_threshold;
+mod migration;
+mod nonce;
+mod position_nft;
+mod reclaim_dormant;
+mod reentrancy;
+mod reverse_stream;
+mod schedule_orderings;
+mod sender_pool;
 mod setup;
+mod sweep_token;
+mod template;
+mod topup_lockup;
+mod tranched_vesting;
+mod transfer_receiver;
+mod utils;
 mod withdraw_stream;
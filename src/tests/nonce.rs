@@ -0,0 +1,62 @@
+use super::setup::{SetupStreamTest, StreamFields};
+
+#[test]
+fn test_withdraw_lockup_with_nonce_should_apply_a_nonce_once() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let receiver = vars.contract.get_lockup(&id).receiver;
+
+    vars.move_ledger_timestamp_to(30);
+
+    let first = vars.contract.withdraw_lockup_with_nonce(&id, &100, &7);
+    let second = vars.contract.withdraw_lockup_with_nonce(&id, &100, &7);
+
+    assert_eq!(first, 100);
+    assert_eq!(second, 100);
+    assert_eq!(vars.token.balance(&receiver), 100);
+    assert_eq!(vars.contract.get_lockup_withdrawn(&id), 100);
+
+    let third = vars.contract.withdraw_lockup_with_nonce(&id, &100, &8);
+
+    assert_eq!(third, 100);
+    assert_eq!(vars.token.balance(&receiver), 200);
+}
+
+#[test]
+fn test_withdraw_lockup_with_nonce_should_forget_old_nonces() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let receiver = vars.contract.get_lockup(&id).receiver;
+
+    vars.move_ledger_timestamp_to(50);
+
+    for nonce in 0..11 {
+        vars.contract.withdraw_lockup_with_nonce(&id, &10, &nonce);
+    }
+
+    assert_eq!(vars.token.balance(&receiver), 110);
+
+    vars.contract.withdraw_lockup_with_nonce(&id, &10, &10);
+    assert_eq!(vars.token.balance(&receiver), 110);
+
+    vars.contract.withdraw_lockup_with_nonce(&id, &10, &0);
+    assert_eq!(vars.token.balance(&receiver), 120);
+}
+
+#[test]
+fn test_cancel_lockup_with_nonce_should_apply_a_nonce_once() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let receiver = vars.contract.get_lockup(&id).receiver;
+
+    vars.move_ledger_timestamp_to(40);
+
+    let first = vars.contract.cancel_lockup_with_nonce(&id, &true, &3);
+    let second = vars.contract.cancel_lockup_with_nonce(&id, &true, &3);
+
+    assert_eq!(first, (600, 400));
+    assert_eq!(second, first);
+    assert_eq!(vars.token.balance(&receiver), 400);
+    assert_eq!(vars.token.balance(&vars.admin), 600);
+    assert!(vars
+        .contract
+        .try_cancel_lockup_with_nonce(&id, &true, &4)
+        .is_err());
+}
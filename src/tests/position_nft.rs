@@ -0,0 +1,106 @@
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{LockupInput, LockupOptions, Position},
+};
+
+use super::setup::SetupStreamTest;
+
+#[contract]
+pub struct MockNft;
+
+#[contractimpl]
+impl MockNft {
+    pub fn mint(e: Env, to: Address, token_id: u64) {
+        e.storage().persistent().set(&token_id, &to);
+    }
+
+    pub fn owner_of(e: Env, token_id: u64) -> Address {
+        e.storage().persistent().get(&token_id).unwrap()
+    }
+
+    pub fn transfer(e: Env, from: Address, to: Address, token_id: u64) {
+        from.require_auth();
+
+        assert_eq!(Self::owner_of(e.clone(), token_id), from);
+
+        e.storage().persistent().set(&token_id, &to);
+    }
+}
+
+fn create_tokenized_stream(vars: &SetupStreamTest) -> Result<u64, CustomErrors> {
+    let params = LockupInput {
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        options: LockupOptions {
+            tokenized: true,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    match vars.contract.try_create_stream(&params) {
+        Ok(id) => Ok(id.unwrap()),
+        Err(error) => Err(error.unwrap()),
+    }
+}
+
+fn setup_with_nft<'a>(vars: &SetupStreamTest<'a>) -> MockNftClient<'a> {
+    let nft = MockNftClient::new(&vars.env, &vars.env.register_contract(None, MockNft));
+
+    vars.initialize();
+    vars.contract.set_position_nft(&nft.address);
+
+    nft
+}
+
+#[test]
+fn test_tokenized_stream_should_mint_the_position_to_the_receiver() {
+    let vars = SetupStreamTest::setup(1000);
+    let nft = setup_with_nft(&vars);
+
+    let id = create_tokenized_stream(&vars).unwrap();
+    let lockup = vars.contract.get_lockup(&id);
+
+    assert_eq!(
+        lockup.position,
+        Position::Tokenized(nft.address.clone(), id)
+    );
+    assert_eq!(nft.owner_of(&id), lockup.receiver);
+}
+
+#[test]
+fn test_tokenized_stream_should_follow_the_nft_owner() {
+    let vars = SetupStreamTest::setup(1000);
+    let nft = setup_with_nft(&vars);
+
+    let id = create_tokenized_stream(&vars).unwrap();
+    let receiver = vars.contract.get_lockup(&id).receiver;
+    let new_receiver = Address::generate(&vars.env);
+
+    let result = vars
+        .contract
+        .try_transfer_lockup_receiver(&id, &new_receiver);
+
+    assert_eq!(result, Err(Ok(CustomErrors::PositionOwnerMismatch)));
+
+    nft.transfer(&receiver, &new_receiver, &id);
+    vars.contract.transfer_lockup_receiver(&id, &new_receiver);
+
+    vars.move_ledger_timestamp_to(100);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.token.balance(&new_receiver), 1000);
+}
+
+#[test]
+fn test_tokenized_stream_should_revert_without_a_position_nft() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let result = create_tokenized_stream(&vars);
+
+    assert_eq!(result, Err(CustomErrors::PositionNftNotSet));
+}
@@ -0,0 +1,91 @@
+use soroban_sdk::{symbol_short, testutils::Events, IntoVal, Val, Vec};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{LockupInput, LockupOptions, LockupStatus},
+};
+
+use super::setup::{SetupStreamTest, StreamFields};
+
+#[test]
+fn test_reclaim_dormant_should_return_unwithdrawn_funds_to_the_sender() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+    let lockup = vars.contract.get_lockup(&id);
+
+    vars.initialize();
+    vars.contract.set_dormancy_period(&1000);
+
+    vars.move_ledger_timestamp_to(30);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    let sender_balance = vars.token.balance(&lockup.sender);
+
+    vars.move_ledger_timestamp_to(1101);
+
+    assert_eq!(vars.contract.reclaim_dormant(&id), 700);
+    assert_eq!(vars.token.balance(&lockup.sender), sender_balance + 700);
+    assert_eq!(vars.token.balance(&lockup.receiver), 300);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+    assert_eq!(vars.contract.get_lockup_status(&id), LockupStatus::Settled);
+    assert_eq!(vars.contract.get_lockup_withdrawn(&id), 300);
+    assert_eq!(vars.contract.get_lockup(&id).refunded, 700);
+
+    let result = vars.contract.try_reclaim_dormant(&id);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupAlreadySettled)));
+}
+
+#[test]
+fn test_reclaim_dormant_should_revert_within_the_dormancy_period() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.initialize();
+    vars.contract.set_dormancy_period(&1000);
+
+    vars.move_ledger_timestamp_to(1100);
+
+    let result = vars.contract.try_reclaim_dormant(&id);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupNotDormant)));
+}
+
+#[test]
+fn test_get_dormancy_period_should_default_to_two_years() {
+    let vars = SetupStreamTest::setup(1000);
+
+    assert_eq!(vars.contract.get_dormancy_period(), 2 * 365 * 24 * 60 * 60);
+}
+
+#[test]
+fn test_reclaim_dormant_should_not_settle_an_underfunded_lockup() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+    vars.contract.set_dormancy_period(&1000);
+
+    let id = vars.contract.create_stream(&LockupInput {
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        options: LockupOptions {
+            initial_deposit: Some(400),
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    });
+
+    vars.move_ledger_timestamp_to(1101);
+
+    assert_eq!(vars.contract.reclaim_dormant(&id), 400);
+    assert!(!vars.contract.is_lockup_settled(&id));
+
+    let settled: Vec<Val> = (symbol_short!("LOCKUP"), symbol_short!("SETTLED")).into_val(&vars.env);
+
+    assert!(!vars
+        .env
+        .events()
+        .all()
+        .iter()
+        .any(|(_, topics, _)| topics == settled));
+}
@@ -0,0 +1,102 @@
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+use crate::base::{contract::FluxityClient, errors::CustomErrors, storage, types::LockupInput};
+
+use super::setup::{SetupStreamTest, StreamFields};
+
+/// A token calling back into the lockup contract when it transfers
+#[contract]
+pub struct MockReentrantToken;
+
+#[contractimpl]
+impl MockReentrantToken {
+    pub fn set_target(e: Env, lockups: Address, id: u64) {
+        e.storage()
+            .instance()
+            .set(&symbol_short!("lockups"), &lockups);
+        e.storage().instance().set(&symbol_short!("id"), &id);
+    }
+
+    pub fn refused(e: Env) -> bool {
+        e.storage()
+            .instance()
+            .get(&symbol_short!("refused"))
+            .unwrap_or(false)
+    }
+
+    pub fn transfer_from(_e: Env, _spender: Address, _from: Address, _to: Address, _amount: i128) {}
+
+    pub fn transfer(e: Env, _from: Address, _to: Address, _amount: i128) {
+        let Some(lockups) = e
+            .storage()
+            .instance()
+            .get::<_, Address>(&symbol_short!("lockups"))
+        else {
+            return;
+        };
+        let id: u64 = e.storage().instance().get(&symbol_short!("id")).unwrap();
+
+        let result = FluxityClient::new(&e, &lockups).try_withdraw_lockup(&id, &0);
+
+        // A host error rather than a contract one, the call never reaches the lock
+        e.storage()
+            .instance()
+            .set(&symbol_short!("refused"), &matches!(result, Err(Err(_))));
+    }
+}
+
+#[test]
+fn test_withdraw_and_cancel_should_revert_while_the_lock_is_held() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.env.as_contract(&vars.contract.address, || {
+        storage::acquire_reentrancy_lock(&vars.env).unwrap();
+    });
+
+    vars.move_ledger_timestamp_to(50);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+    assert_eq!(result, Err(Ok(CustomErrors::Reentrancy)));
+
+    let result = vars.contract.try_cancel_lockup(&id, &true);
+    assert_eq!(result, Err(Ok(CustomErrors::Reentrancy)));
+}
+
+#[test]
+fn test_withdraw_should_release_the_lock() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 500);
+
+    vars.move_ledger_timestamp_to(60);
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 100);
+}
+
+#[test]
+fn test_reentrant_token_should_be_refused_by_the_host() {
+    let vars = SetupStreamTest::setup(1000);
+    let token = MockReentrantTokenClient::new(
+        &vars.env,
+        &vars.env.register_contract(None, MockReentrantToken),
+    );
+
+    let params = LockupInput {
+        token: token.address.clone(),
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        ..vars.lockup_input()
+    };
+
+    let id = vars.contract.create_stream(&params);
+
+    token.set_target(&vars.contract.address, &id);
+
+    vars.move_ledger_timestamp_to(50);
+
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 500);
+    assert!(token.refused());
+    assert_eq!(vars.contract.get_lockup(&id).withdrawn, 500);
+}
@@ -0,0 +1,91 @@
+use crate::base::{
+    errors::CustomErrors,
+    types::{LockupInput, LockupOptions, StreamMode},
+};
+
+use super::setup::SetupStreamTest;
+
+fn reverse_params(vars: &SetupStreamTest) -> LockupInput {
+    LockupInput {
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        options: LockupOptions {
+            mode: StreamMode::Reverse,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    }
+}
+
+#[test]
+fn test_reverse_stream_should_grow_the_sender_refundable_over_time() {
+    let vars = SetupStreamTest::setup(1000);
+    let id = vars.contract.create_stream(&reverse_params(&vars));
+
+    for (timestamp, refundable) in [(10, 100), (50, 500), (90, 900)] {
+        vars.move_ledger_timestamp_to(timestamp);
+
+        assert_eq!(vars.contract.get_refundable_to_sender(&id), refundable);
+        assert_eq!(vars.contract.get_withdrawable(&id), 1000 - refundable);
+    }
+}
+
+#[test]
+fn test_reverse_stream_should_keep_what_the_receiver_claimed() {
+    let vars = SetupStreamTest::setup(1000);
+    let params = reverse_params(&vars);
+    let receiver = params.receiver.clone();
+    let id = vars.contract.create_stream(&params);
+
+    vars.move_ledger_timestamp_to(20);
+
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 800);
+
+    vars.move_ledger_timestamp_to(60);
+
+    assert_eq!(vars.contract.get_withdrawable(&id), 0);
+    assert_eq!(vars.contract.get_refundable_to_sender(&id), 200);
+    assert_eq!(vars.contract.cancel_lockup(&id, &true), (200, 0));
+    assert_eq!(vars.token.balance(&receiver), 800);
+    assert_eq!(vars.token.balance(&vars.admin), 200);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+}
+
+#[test]
+fn test_reverse_stream_should_let_the_sender_take_back_the_unclaimed_amount_after_the_end() {
+    let vars = SetupStreamTest::setup(1000);
+    let id = vars.contract.create_stream(&reverse_params(&vars));
+
+    vars.move_ledger_timestamp_to(150);
+
+    assert_eq!(vars.contract.get_withdrawable(&id), 0);
+    assert!(!vars.contract.is_lockup_settled(&id));
+    assert_eq!(vars.contract.cancel_lockup(&id, &true), (1000, 0));
+    assert_eq!(vars.token.balance(&vars.admin), 1000);
+    assert!(vars.contract.is_lockup_settled(&id));
+}
+
+#[test]
+fn test_reverse_stream_should_revert_for_vestings_and_cliffs() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = LockupInput {
+        end_date: 90 * 86400,
+        ..reverse_params(&vars)
+    };
+
+    let result = vars.contract.try_create_vesting(&params);
+
+    assert_eq!(result, Err(Ok(CustomErrors::UnsupportedForVesting)));
+
+    let params = LockupInput {
+        cliff_date: 10,
+        ..reverse_params(&vars)
+    };
+
+    let result = vars.contract.try_create_stream(&params);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidCliffDate)));
+}
@@ -0,0 +1,119 @@
+use super::setup::{SetupStreamTest, StreamFields};
+
+// Every ordering of the start, cliff and cancellable dates which passes the
+// validation, the end date is always 100
+const ORDERINGS: [(u64, u64, u64); 12] = [
+    (0, 0, 0),
+    (0, 0, 50),
+    (0, 50, 50),
+    (0, 50, 20),
+    (0, 20, 50),
+    (10, 10, 0),
+    (10, 50, 5),
+    (10, 30, 60),
+    (10, 100, 50),
+    (0, 100, 100),
+    (0, 0, 100),
+    (10, 60, 30),
+];
+
+fn stream_fields(start_date: u64, cliff_date: u64, cancellable_date: u64) -> StreamFields {
+    StreamFields {
+        amount: 1000,
+        start_date,
+        end_date: 100,
+        cliff_date,
+        cancellable_date,
+    }
+}
+
+#[test]
+fn test_withdrawable_should_stay_within_bounds_for_every_ordering() {
+    for (start_date, cliff_date, cancellable_date) in ORDERINGS {
+        let (vars, id) = SetupStreamTest::setup_with_stream_created(stream_fields(
+            start_date,
+            cliff_date,
+            cancellable_date,
+        ));
+
+        let mut previous = 0;
+
+        for timestamp in [0, 5, 10, 20, 30, 50, 60, 99, 100, 150] {
+            vars.move_ledger_timestamp_to(timestamp);
+
+            let withdrawable = vars.contract.get_withdrawable(&id);
+
+            if timestamp <= cliff_date {
+                assert_eq!(withdrawable, 0);
+            }
+
+            assert!(withdrawable >= previous && withdrawable <= 1000);
+
+            previous = withdrawable;
+        }
+
+        assert_eq!(previous, 1000);
+    }
+}
+
+#[test]
+fn test_cancel_should_split_the_whole_amount_for_every_ordering() {
+    for (start_date, cliff_date, cancellable_date) in ORDERINGS {
+        for cancel_date in [cancellable_date, cancellable_date.max(cliff_date), 99] {
+            if cancel_date < cancellable_date || cancel_date >= 100 {
+                continue;
+            }
+
+            let (vars, id) = SetupStreamTest::setup_with_stream_created(stream_fields(
+                start_date,
+                cliff_date,
+                cancellable_date,
+            ));
+            let lockup = vars.contract.get_lockup(&id);
+
+            vars.move_ledger_timestamp_to(cancel_date);
+
+            // Withdrawing up to the start date reverts with LockupNotStartedYet
+            let withdrawn = if cancel_date > start_date {
+                vars.contract.withdraw_lockup(&id, &0)
+            } else {
+                0
+            };
+            let (sender_amount, receiver_amount) = vars.contract.cancel_lockup(&id, &true);
+
+            assert!(sender_amount >= 0 && receiver_amount >= 0);
+            assert_eq!(sender_amount + receiver_amount + withdrawn, 1000);
+
+            if cancel_date <= cliff_date {
+                assert_eq!(withdrawn + receiver_amount, 0);
+            }
+
+            assert_eq!(vars.contract.get_withdrawable(&id), 0);
+            assert_eq!(
+                vars.token.balance(&lockup.receiver),
+                withdrawn + receiver_amount
+            );
+            assert_eq!(vars.token.balance(&vars.contract.address), 0);
+        }
+    }
+}
+
+#[test]
+fn test_cancel_should_revert_before_the_cancellable_date_for_every_ordering() {
+    for (start_date, cliff_date, cancellable_date) in ORDERINGS {
+        if cancellable_date == 0 {
+            continue;
+        }
+
+        let (vars, id) = SetupStreamTest::setup_with_stream_created(stream_fields(
+            start_date,
+            cliff_date,
+            cancellable_date,
+        ));
+
+        vars.move_ledger_timestamp_to(cancellable_date - 1);
+
+        assert!(vars.contract.try_cancel_lockup(&id, &true).is_err());
+        assert_eq!(vars.contract.get_refundable_to_sender(&id), 0);
+    }
+}
@@ -0,0 +1,128 @@
+use soroban_sdk::{testutils::Address as _, Address};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{LockupInput, LockupOptions},
+};
+
+use super::setup::SetupStreamTest;
+
+fn create_stream_from_pool(vars: &SetupStreamTest, amount: i128) -> Result<u64, CustomErrors> {
+    let receiver = Address::generate(&vars.env);
+
+    let params = LockupInput {
+        receiver,
+        amount,
+        options: LockupOptions {
+            from_pool: true,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    match vars.contract.try_create_stream(&params) {
+        Ok(id) => Ok(id.unwrap()),
+        Err(error) => Err(error.unwrap()),
+    }
+}
+
+#[test]
+fn test_deposit_should_fill_the_sender_pool() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let balance = vars
+        .contract
+        .deposit(&vars.admin, &vars.token.address, &1000);
+
+    assert_eq!(balance, 1000);
+    assert_eq!(
+        vars.contract
+            .get_sender_pool(&vars.admin, &vars.token.address),
+        1000
+    );
+    assert_eq!(vars.token.balance(&vars.admin), 0);
+    assert_eq!(vars.token.balance(&vars.contract.address), 1000);
+}
+
+#[test]
+fn test_create_stream_should_draw_from_the_pool() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.contract
+        .deposit(&vars.admin, &vars.token.address, &1000);
+
+    let id0 = create_stream_from_pool(&vars, 300).unwrap();
+    let id1 = create_stream_from_pool(&vars, 300).unwrap();
+
+    assert_eq!(vars.contract.get_lockup(&id0).funded_amount, 300);
+    assert_eq!(vars.contract.get_lockup(&id1).funded_amount, 300);
+    assert_eq!(
+        vars.contract
+            .get_sender_pool(&vars.admin, &vars.token.address),
+        400
+    );
+    assert_eq!(vars.token.balance(&vars.contract.address), 1000);
+}
+
+#[test]
+fn test_create_stream_should_revert_when_pool_is_insufficient() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.contract
+        .deposit(&vars.admin, &vars.token.address, &500);
+
+    let result = create_stream_from_pool(&vars, 600);
+
+    assert_eq!(result, Err(CustomErrors::InsufficientPoolBalance));
+    assert_eq!(
+        vars.contract
+            .get_sender_pool(&vars.admin, &vars.token.address),
+        500
+    );
+}
+
+#[test]
+fn test_withdraw_pool_should_reclaim_the_remainder() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.contract
+        .deposit(&vars.admin, &vars.token.address, &1000);
+
+    create_stream_from_pool(&vars, 700).unwrap();
+
+    let remaining = vars
+        .contract
+        .withdraw_pool(&vars.admin, &vars.token.address, &300);
+
+    assert_eq!(remaining, 0);
+    assert_eq!(vars.token.balance(&vars.admin), 300);
+    assert_eq!(vars.token.balance(&vars.contract.address), 700);
+}
+
+#[test]
+fn test_withdraw_pool_should_revert_when_amount_exceeds_pool() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.contract
+        .deposit(&vars.admin, &vars.token.address, &500);
+
+    let result = vars
+        .contract
+        .try_withdraw_pool(&vars.admin, &vars.token.address, &501);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InsufficientPoolBalance)));
+}
+
+#[test]
+fn test_sweep_token_should_not_take_pooled_tokens() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+    vars.contract
+        .deposit(&vars.admin, &vars.token.address, &500);
+
+    let swept = vars.contract.sweep_token(&vars.token.address, &vars.admin);
+
+    assert_eq!(swept, 0);
+    assert_eq!(vars.token.balance(&vars.contract.address), 500);
+}
@@ -5,7 +5,10 @@ use soroban_sdk::{
 };
 
 use crate::{
-    base::types::{LockupInput, Rate},
+    base::{
+        storage,
+        types::{ContractConfig, Lockup, LockupInput, Rate},
+    },
     Fluxity, FluxityClient,
 };
 
@@ -103,11 +106,12 @@ impl<'a> SetupStreamTest<'a> {
             end_date: now + fields.end_date,
             cancellable_date: now + fields.cancellable_date,
             rate: crate::base::types::Rate::Monthly,
+            options: Default::default(),
         };
 
         let id = vars.contract.create_stream(&params);
 
-        assert_eq!(vars.contract.get_lockup(&0).sender, vars.admin.clone());
+        assert_eq!(vars.contract.get_lockup(&id).sender, vars.admin.clone());
         assert_eq!(vars.token.decimals(), 7);
         assert_eq!(vars.token.balance(&vars.admin), 0);
         assert_eq!(vars.token.balance(&vars.contract.address), vars.amount);
@@ -131,6 +135,7 @@ impl<'a> SetupStreamTest<'a> {
             cancellable_date: now + fields.cancellable_date,
             start_date: now + fields.start_date,
             token: vars.token.address.clone(),
+            options: Default::default(),
         };
 
         let id = vars.contract.create_vesting(&params);
@@ -138,10 +143,49 @@ impl<'a> SetupStreamTest<'a> {
         (vars, id)
     }
 
+    /// Builds the params of a 1000 token monthly stream from the admin to a new
+    /// receiver, running for 100 seconds from the current timestamp. Tests override
+    /// the fields they need with the struct update syntax
+    pub fn lockup_input(&self) -> LockupInput {
+        let now = self.env.ledger().timestamp();
+
+        LockupInput {
+            sender: self.admin.clone(),
+            receiver: Address::generate(&self.env),
+            token: self.token.address.clone(),
+            amount: 1000,
+            cancellable_date: now,
+            cliff_date: now,
+            start_date: now,
+            end_date: now + 100,
+            rate: Rate::Monthly,
+            options: Default::default(),
+        }
+    }
+
     pub fn move_ledger_timestamp_to(&self, timestamp: u64) {
         self.env.ledger().set(LedgerInfo {
             timestamp,
             ..self.env.ledger().get()
         });
     }
+
+    pub fn default_config(&self) -> ContractConfig {
+        ContractConfig {
+            fee_bps: 0,
+            fee_recipient: self.admin.clone(),
+            min_duration: 0,
+        }
+    }
+
+    pub fn initialize(&self) {
+        self.contract
+            .initialize(&self.admin, &self.default_config());
+    }
+
+    pub fn set_lockup(&self, id: u64, lockup: &Lockup) {
+        self.env.as_contract(&self.contract.address, || {
+            storage::set_lockup(&self.env, id, lockup);
+        });
+    }
 }
@@ -0,0 +1,112 @@
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address};
+
+use crate::base::{errors::CustomErrors, storage};
+
+use super::setup::{SetupStreamTest, StreamFields};
+
+#[test]
+fn test_sweep_token_should_transfer_only_the_surplus() {
+    let (vars, _) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.initialize();
+
+    StellarAssetClient::new(&vars.env, &vars.token.address).mint(&vars.contract.address, &300);
+
+    let to = Address::generate(&vars.env);
+    let swept = vars.contract.sweep_token(&vars.token.address, &to);
+
+    assert_eq!(swept, 300);
+    assert_eq!(vars.token.balance(&to), 300);
+    assert_eq!(vars.token.balance(&vars.contract.address), vars.amount);
+}
+
+#[test]
+fn test_sweep_token_should_not_touch_withdrawn_or_cancelled_lockups() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.initialize();
+
+    StellarAssetClient::new(&vars.env, &vars.token.address).mint(&vars.contract.address, &300);
+
+    vars.move_ledger_timestamp_to(20);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.cancel_lockup(&id, &true);
+
+    let to = Address::generate(&vars.env);
+    let swept = vars.contract.sweep_token(&vars.token.address, &to);
+
+    assert_eq!(swept, 300);
+    assert_eq!(vars.token.balance(&to), 300);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+}
+
+#[test]
+fn test_sweep_token_should_return_zero_when_there_is_no_surplus() {
+    let (vars, _) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.initialize();
+
+    let to = Address::generate(&vars.env);
+    let swept = vars.contract.sweep_token(&vars.token.address, &to);
+
+    assert_eq!(swept, 0);
+    assert_eq!(vars.token.balance(&vars.contract.address), vars.amount);
+}
+
+#[test]
+fn test_sweep_token_should_revert_when_not_initialized() {
+    let (vars, _) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let to = Address::generate(&vars.env);
+    let result = vars.contract.try_sweep_token(&vars.token.address, &to);
+
+    assert_eq!(result, Err(Ok(CustomErrors::NotInitialized)));
+}
+
+#[test]
+fn test_get_locked_balance_should_follow_withdrawals_and_refunds() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 1000);
+
+    vars.move_ledger_timestamp_to(30);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 700);
+
+    vars.move_ledger_timestamp_to(40);
+    vars.contract.cancel_lockup(&id, &true);
+
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 0);
+    assert_eq!(vars.token.balance(&vars.contract.address), 0);
+}
+
+#[test]
+fn test_get_locked_balance_should_keep_unclaimed_amounts_after_cancel() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(40);
+    vars.contract.cancel_lockup(&id, &false);
+
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 400);
+
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 0);
+}
+
+#[test]
+fn test_locked_balance_should_not_go_below_zero() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.env.as_contract(&vars.contract.address, || {
+        storage::decrease_locked_balance(&vars.env, &vars.token.address, &400);
+    });
+
+    vars.move_ledger_timestamp_to(100);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.contract.get_locked_balance(&vars.token.address), 0);
+}
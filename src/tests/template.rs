@@ -0,0 +1,123 @@
+use soroban_sdk::{testutils::Address as _, Address};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{LockupTemplate, Rate},
+};
+
+use super::setup::SetupStreamTest;
+
+#[test]
+fn test_template_should_be_registered() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let template = LockupTemplate {
+        sender: vars.admin.clone(),
+        token: vars.token.address.clone(),
+        rate: Rate::Monthly,
+        cliff_duration: 10,
+        cancellable: true,
+    };
+
+    let id = vars.contract.register_template(&template);
+
+    assert_eq!(id, 0);
+    assert_eq!(vars.contract.get_template(&id), template);
+}
+
+#[test]
+fn test_two_streams_should_be_created_from_template() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let template = LockupTemplate {
+        sender: vars.admin.clone(),
+        token: vars.token.address.clone(),
+        rate: Rate::Monthly,
+        cliff_duration: 10,
+        cancellable: false,
+    };
+
+    let template_id = vars.contract.register_template(&template);
+
+    let receiver0 = Address::generate(&vars.env);
+    let receiver1 = Address::generate(&vars.env);
+    let now = vars.env.ledger().timestamp();
+
+    let id0 = vars.contract.create_stream_from_template(
+        &template_id,
+        &receiver0,
+        &1500,
+        &now,
+        &(now + 100),
+    );
+    let id1 = vars.contract.create_stream_from_template(
+        &template_id,
+        &receiver1,
+        &500,
+        &(now + 50),
+        &(now + 200),
+    );
+
+    let stream0 = vars.contract.get_lockup(&id0);
+    let stream1 = vars.contract.get_lockup(&id1);
+
+    assert_eq!(id0, 1);
+    assert_eq!(id1, 2);
+    assert_eq!(vars.token.balance(&vars.admin), 0);
+    assert_eq!(vars.token.balance(&vars.contract.address), 2000);
+
+    assert_eq!(stream0.sender, vars.admin);
+    assert_eq!(stream0.receiver, receiver0);
+    assert_eq!(stream0.token, vars.token.address);
+    assert_eq!(stream0.amount, 1500);
+    assert_eq!(stream0.rate, Rate::Monthly);
+    assert_eq!(stream0.cliff_date, now + 10);
+    assert_eq!(stream0.cancellable_date, now + 100);
+    assert!(!stream0.is_vesting);
+
+    assert_eq!(stream1.receiver, receiver1);
+    assert_eq!(stream1.amount, 500);
+    assert_eq!(stream1.start_date, now + 50);
+    assert_eq!(stream1.cliff_date, now + 60);
+    assert_eq!(stream1.end_date, now + 200);
+}
+
+#[test]
+fn test_create_stream_from_template_should_revert_when_template_does_not_exist() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let receiver = Address::generate(&vars.env);
+    let now = vars.env.ledger().timestamp();
+
+    let result =
+        vars.contract
+            .try_create_stream_from_template(&0, &receiver, &1000, &now, &(now + 100));
+
+    assert_eq!(result, Err(Ok(CustomErrors::TemplateNotFound)));
+}
+
+#[test]
+fn test_create_stream_from_template_should_revert_when_the_cliff_overflows() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let template_id = vars.contract.register_template(&LockupTemplate {
+        sender: vars.admin.clone(),
+        token: vars.token.address.clone(),
+        rate: Rate::Monthly,
+        cliff_duration: u64::MAX,
+        cancellable: false,
+    });
+
+    let receiver = Address::generate(&vars.env);
+    let now = vars.env.ledger().timestamp();
+
+    let result = vars.contract.try_create_stream_from_template(
+        &template_id,
+        &receiver,
+        &1000,
+        &(now + 1),
+        &(now + 100),
+    );
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidDuration)));
+}
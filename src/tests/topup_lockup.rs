@@ -0,0 +1,41 @@
+use soroban_sdk::{symbol_short, testutils::Events, IntoVal};
+
+use crate::base::errors::CustomErrors;
+
+use super::setup::{SetupStreamTest, StreamFields, VestingFields};
+
+#[test]
+fn test_topup_lockup_should_extend_the_amount_and_end_date() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let amount = vars.contract.topup_lockup(&id, &500);
+    let lockup = vars.contract.get_lockup(&id);
+
+    assert_eq!(amount, 1500);
+    assert_eq!(lockup.amount, 1500);
+    assert_eq!(lockup.end_date, 150);
+}
+
+#[test]
+fn test_topup_lockup_should_emit_the_added_and_new_amounts() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.contract.topup_lockup(&id, &500);
+
+    let events = vars.env.events().all();
+    assert!(events.contains((
+        vars.contract.address.clone(),
+        (symbol_short!("LOCKUP"), symbol_short!("TOPUP")).into_val(&vars.env),
+        (id, 1u64, 500i128, 1500i128).into_val(&vars.env)
+    )));
+}
+
+#[test]
+fn test_topup_lockup_should_revert_for_vestings() {
+    let (vars, id) = SetupStreamTest::setup_with_vesting_created(VestingFields::default());
+
+    let result = vars.contract.try_topup_lockup(&id, &500);
+
+    assert_eq!(result, Err(Ok(CustomErrors::UnsupportedForVesting)));
+    assert_eq!(vars.contract.get_lockup(&id).amount, 1000);
+}
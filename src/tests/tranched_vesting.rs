@@ -0,0 +1,142 @@
+use soroban_sdk::{vec, Vec};
+
+use super::setup::SetupStreamTest;
+use crate::base::{
+    errors,
+    types::{LockupInput, LockupKind, Rate},
+};
+
+fn tranched_params(vars: &SetupStreamTest) -> LockupInput {
+    let now = vars.env.ledger().timestamp();
+
+    LockupInput {
+        end_date: now + 400,
+        rate: Rate::Daily,
+        ..vars.lockup_input()
+    }
+}
+
+fn quarterly_tranches(vars: &SetupStreamTest) -> Vec<(u64, i128)> {
+    vec![&vars.env, (100, 250), (200, 250), (300, 250), (400, 250)]
+}
+
+#[test]
+fn test_tranched_vesting_should_be_created() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = tranched_params(&vars);
+    let tranches = quarterly_tranches(&vars);
+
+    let id = vars.contract.create_tranched_vesting(&params, &tranches);
+
+    let lockup = vars.contract.get_lockup(&id);
+
+    assert!(lockup.is_vesting);
+    assert_eq!(lockup.tranches, tranches);
+    assert_eq!(vars.contract.get_lockup_kind(&id), LockupKind::Tranched);
+    assert_eq!(vars.token.balance(&vars.contract.address), 1000);
+}
+
+#[test]
+fn test_tranched_vesting_should_unlock_at_each_tranche_boundary() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = tranched_params(&vars);
+    let id = vars
+        .contract
+        .create_tranched_vesting(&params, &quarterly_tranches(&vars));
+
+    vars.move_ledger_timestamp_to(99);
+    assert_eq!(vars.contract.get_withdrawable(&id), 0);
+
+    vars.move_ledger_timestamp_to(100);
+    assert_eq!(vars.contract.get_withdrawable(&id), 250);
+
+    vars.move_ledger_timestamp_to(199);
+    assert_eq!(vars.contract.get_withdrawable(&id), 250);
+
+    vars.move_ledger_timestamp_to(200);
+    assert_eq!(vars.contract.get_withdrawable(&id), 500);
+
+    vars.move_ledger_timestamp_to(300);
+    assert_eq!(vars.contract.get_withdrawable(&id), 750);
+
+    vars.move_ledger_timestamp_to(400);
+    assert_eq!(vars.contract.get_withdrawable(&id), 1000);
+}
+
+#[test]
+fn test_tranched_vesting_should_be_withdrawn_per_tranche() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = tranched_params(&vars);
+    let receiver = params.receiver.clone();
+    let id = vars
+        .contract
+        .create_tranched_vesting(&params, &quarterly_tranches(&vars));
+
+    vars.move_ledger_timestamp_to(150);
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 250);
+
+    vars.move_ledger_timestamp_to(250);
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 250);
+
+    assert_eq!(vars.token.balance(&receiver), 500);
+    assert_eq!(vars.token.balance(&vars.contract.address), 500);
+}
+
+#[test]
+fn test_tranched_vesting_should_revert_when_tranches_do_not_sum_to_amount() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = tranched_params(&vars);
+    let tranches = vec![&vars.env, (100, 250), (200, 250)];
+
+    let result = vars
+        .contract
+        .try_create_tranched_vesting(&params, &tranches);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::InvalidTranches)));
+}
+
+#[test]
+fn test_tranched_vesting_should_revert_when_tranches_are_not_sorted() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = tranched_params(&vars);
+    let tranches = vec![&vars.env, (200, 500), (100, 500)];
+
+    let result = vars
+        .contract
+        .try_create_tranched_vesting(&params, &tranches);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::InvalidTranches)));
+}
+
+#[test]
+fn test_tranched_vesting_should_revert_when_tranches_are_outside_the_span() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = tranched_params(&vars);
+    let tranches = vec![&vars.env, (100, 500), (500, 500)];
+
+    let result = vars
+        .contract
+        .try_create_tranched_vesting(&params, &tranches);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::InvalidTranches)));
+}
+
+#[test]
+fn test_tranched_vesting_should_revert_when_tranches_are_empty() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = tranched_params(&vars);
+    let tranches = vec![&vars.env];
+
+    let result = vars
+        .contract
+        .try_create_tranched_vesting(&params, &tranches);
+
+    assert_eq!(result, Err(Ok(errors::CustomErrors::InvalidTranches)));
+}
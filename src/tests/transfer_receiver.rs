@@ -0,0 +1,136 @@
+use soroban_sdk::{testutils::Address as _, vec, Address};
+
+use crate::base::{
+    errors::CustomErrors,
+    types::{LockupInput, LockupOptions},
+};
+
+use super::setup::{SetupStreamTest, StreamFields};
+
+#[test]
+fn test_transfer_lockup_receiver_should_move_the_lockup() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let old_receiver = vars.contract.get_lockup(&id).receiver;
+    let new_receiver = Address::generate(&vars.env);
+
+    vars.contract.transfer_lockup_receiver(&id, &new_receiver);
+
+    assert_eq!(vars.contract.get_lockup(&id).receiver, new_receiver);
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.token.balance(&new_receiver), 500);
+    assert_eq!(vars.token.balance(&old_receiver), 0);
+    assert_eq!(
+        vars.contract
+            .get_lockups_over_threshold(&new_receiver, &0, &0, &10)
+            .0,
+        vec![&vars.env]
+    );
+
+    vars.move_ledger_timestamp_to(60);
+
+    assert_eq!(
+        vars.contract
+            .get_lockups_over_threshold(&new_receiver, &0, &0, &10)
+            .0,
+        vec![&vars.env, id]
+    );
+    assert_eq!(
+        vars.contract
+            .get_lockups_over_threshold(&old_receiver, &0, &0, &10)
+            .0,
+        vec![&vars.env]
+    );
+}
+
+#[test]
+fn test_transfer_lockup_receiver_should_revert_when_new_receiver_is_the_sender() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let receiver = vars.contract.get_lockup(&id).receiver;
+
+    let result = vars.contract.try_transfer_lockup_receiver(&id, &vars.admin);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidReceiver)));
+    assert_eq!(vars.contract.get_lockup(&id).receiver, receiver);
+}
+
+#[test]
+fn test_transfer_lockup_receiver_should_follow_the_transferable_option() {
+    let vars = SetupStreamTest::setup(2000);
+
+    let params = LockupInput {
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        options: LockupOptions {
+            receiver_transferable: Some(false),
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    let locked_id = vars.contract.create_stream(&params);
+    let receiver = vars.contract.get_lockup(&locked_id).receiver;
+
+    let result = vars
+        .contract
+        .try_transfer_lockup_receiver(&locked_id, &Address::generate(&vars.env));
+
+    assert_eq!(result, Err(Ok(CustomErrors::TransferNotAllowed)));
+    assert_eq!(vars.contract.get_lockup(&locked_id).receiver, receiver);
+
+    let params = LockupInput {
+        options: LockupOptions {
+            receiver_transferable: Some(true),
+            ..Default::default()
+        },
+        ..params
+    };
+
+    let id = vars.contract.create_stream(&params);
+    let new_receiver = Address::generate(&vars.env);
+
+    vars.contract.transfer_lockup_receiver(&id, &new_receiver);
+
+    assert_eq!(vars.contract.get_lockup(&id).receiver, new_receiver);
+}
+
+#[test]
+fn test_transfer_lockup_receiver_should_keep_the_rest_of_the_index() {
+    let vars = SetupStreamTest::setup(3000);
+    let receiver = Address::generate(&vars.env);
+    let mut ids = vec![&vars.env];
+
+    for _ in 0..3 {
+        let params = LockupInput {
+            receiver: receiver.clone(),
+            cancellable_date: 0,
+            cliff_date: 0,
+            start_date: 0,
+            end_date: 100,
+            ..vars.lockup_input()
+        };
+
+        ids.push_back(vars.contract.create_stream(&params));
+    }
+
+    vars.contract
+        .transfer_lockup_receiver(&ids.get_unchecked(0), &Address::generate(&vars.env));
+
+    vars.move_ledger_timestamp_to(50);
+
+    let (remaining, cursor) = vars
+        .contract
+        .get_lockups_over_threshold(&receiver, &0, &0, &10);
+
+    assert_eq!(
+        remaining,
+        vec![&vars.env, ids.get_unchecked(2), ids.get_unchecked(1)]
+    );
+    assert_eq!(cursor, 0);
+}
@@ -0,0 +1,137 @@
+use crate::base::{
+    errors::CustomErrors,
+    types::{Amounts, CliffMode, Rate},
+    utils::{calculate_stream_amounts, calculate_vesting_amounts},
+};
+
+const DAY: u64 = Rate::Daily as u64;
+
+fn amounts(sender_amount: i128, receiver_amount: i128) -> Result<Amounts, CustomErrors> {
+    Ok(Amounts {
+        sender_amount,
+        receiver_amount,
+    })
+}
+
+fn stream(current_date: u64, cliff_mode: CliffMode) -> Result<Amounts, CustomErrors> {
+    // 1000 streamed from 100 to 200 with a cliff at 120
+    calculate_stream_amounts(100, 200, 120, cliff_mode, current_date, 1000)
+}
+
+fn vesting(current_date: u64, align_to_epoch: bool) -> Result<Amounts, CustomErrors> {
+    // 1000 vested daily over 10 days from the middle of day 1, with a 2 day cliff
+    let start_date = DAY + DAY / 2;
+
+    calculate_vesting_amounts(
+        start_date,
+        start_date + 10 * DAY,
+        start_date + 2 * DAY,
+        current_date,
+        Rate::Daily,
+        align_to_epoch,
+        1000,
+    )
+}
+
+#[test]
+fn test_calculate_stream_amounts_should_keep_everything_before_the_cliff() {
+    assert_eq!(stream(50, CliffMode::GateFromStart), amounts(1000, 0));
+    assert_eq!(stream(100, CliffMode::GateFromStart), amounts(1000, 0));
+    assert_eq!(stream(120, CliffMode::GateFromStart), amounts(1000, 0));
+    assert_eq!(stream(120, CliffMode::LinearFromCliff), amounts(1000, 0));
+}
+
+#[test]
+fn test_calculate_stream_amounts_should_accrue_linearly_past_the_cliff() {
+    assert_eq!(stream(121, CliffMode::GateFromStart), amounts(790, 210));
+    assert_eq!(stream(150, CliffMode::GateFromStart), amounts(500, 500));
+    assert_eq!(stream(160, CliffMode::LinearFromCliff), amounts(500, 500));
+    assert_eq!(stream(199, CliffMode::LinearFromCliff), amounts(13, 987));
+}
+
+#[test]
+fn test_calculate_stream_amounts_should_release_everything_from_the_end() {
+    assert_eq!(stream(200, CliffMode::GateFromStart), amounts(0, 1000));
+    assert_eq!(
+        stream(u64::MAX, CliffMode::LinearFromCliff),
+        amounts(0, 1000)
+    );
+}
+
+#[test]
+fn test_calculate_stream_amounts_should_freeze_at_the_cancellation_date() {
+    // Cancelled lockups are evaluated at their cancellation date, however late
+    let cancelled = stream(175, CliffMode::GateFromStart).unwrap();
+
+    assert_eq!(cancelled.sender_amount, 250);
+    assert_eq!(cancelled.receiver_amount, 750);
+    assert_eq!(cancelled.sender_amount + cancelled.receiver_amount, 1000);
+}
+
+#[test]
+fn test_calculate_stream_amounts_should_reject_an_empty_duration() {
+    assert_eq!(
+        calculate_stream_amounts(100, 100, 100, CliffMode::GateFromStart, 150, 1000),
+        Err(CustomErrors::InvalidDuration)
+    );
+}
+
+#[test]
+fn test_calculate_vesting_amounts_should_keep_everything_before_the_cliff() {
+    let start_date = DAY + DAY / 2;
+
+    assert_eq!(vesting(0, false), amounts(1000, 0));
+    assert_eq!(vesting(start_date + DAY, false), amounts(1000, 0));
+    assert_eq!(vesting(start_date + 2 * DAY, false), amounts(1000, 0));
+}
+
+#[test]
+fn test_calculate_vesting_amounts_should_step_by_the_rate() {
+    let start_date = DAY + DAY / 2;
+
+    assert_eq!(vesting(start_date + 2 * DAY + 1, false), amounts(800, 200));
+    assert_eq!(vesting(start_date + 5 * DAY - 1, false), amounts(600, 400));
+    assert_eq!(vesting(start_date + 5 * DAY, false), amounts(500, 500));
+}
+
+#[test]
+fn test_calculate_vesting_amounts_should_step_on_the_epoch_when_aligned() {
+    // Aligned steps fall at midnight, half a day after the unaligned ones
+    let start_date = DAY + DAY / 2;
+
+    assert_eq!(vesting(start_date + 3 * DAY, false), amounts(700, 300));
+    assert_eq!(vesting(start_date + 3 * DAY, true), amounts(750, 250));
+    assert_eq!(vesting(5 * DAY, true), amounts(650, 350));
+}
+
+#[test]
+fn test_calculate_vesting_amounts_should_release_everything_from_the_end() {
+    let start_date = DAY + DAY / 2;
+
+    assert_eq!(vesting(start_date + 10 * DAY, false), amounts(0, 1000));
+    assert_eq!(vesting(start_date + 10 * DAY, true), amounts(0, 1000));
+    assert_eq!(vesting(u64::MAX, false), amounts(0, 1000));
+}
+
+#[test]
+fn test_calculate_vesting_amounts_should_freeze_at_the_cancellation_date() {
+    // Cancelled lockups are evaluated at their cancellation date, however late
+    let start_date = DAY + DAY / 2;
+    let cancelled = vesting(start_date + 6 * DAY + DAY / 3, false).unwrap();
+
+    assert_eq!(
+        cancelled,
+        Amounts {
+            sender_amount: 400,
+            receiver_amount: 600,
+        }
+    );
+}
+
+#[test]
+fn test_calculate_vesting_amounts_should_reject_an_empty_duration() {
+    assert_eq!(
+        calculate_vesting_amounts(100, 100, 100, 150, Rate::Daily, false, 1000),
+        Err(CustomErrors::InvalidDuration)
+    );
+}
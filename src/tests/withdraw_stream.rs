@@ -1,6 +1,15 @@
-use crate::base::errors::CustomErrors;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    vec, Address, IntoVal,
+};
 
-use super::setup::{SetupStreamTest, StreamFields};
+use crate::base::{
+    errors::CustomErrors,
+    types::{CliffMode, LockupInput, LockupOptions, WithdrawalBuckets},
+};
+
+use super::setup::{SetupStreamTest, StreamFields, VestingFields};
 
 #[test]
 fn test_stream_should_be_withdrawable_by_receiver() {
@@ -140,3 +149,561 @@ fn test_withdraw_stream_should_revert_when_amount_is_negative() {
 
     assert_eq!(result, Err(Ok(CustomErrors::AmountUnderflows)));
 }
+
+#[test]
+fn test_withdraw_stream_should_revert_when_duration_is_zero() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let mut stream = vars.contract.get_lockup(&id);
+    stream.end_date = stream.start_date;
+    stream.cliff_date = stream.start_date;
+    vars.set_lockup(id, &stream);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidDuration)));
+}
+
+#[test]
+fn test_withdraw_vesting_should_revert_when_duration_is_zero() {
+    let (vars, id) = SetupStreamTest::setup_with_vesting_created(VestingFields {
+        end_date: 2 * 86400,
+        ..Default::default()
+    });
+
+    let mut vesting = vars.contract.get_lockup(&id);
+    vesting.end_date = vesting.start_date;
+    vesting.cliff_date = vesting.start_date;
+    vars.set_lockup(id, &vesting);
+
+    vars.move_ledger_timestamp_to(86400);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidDuration)));
+}
+
+#[test]
+fn test_withdraw_stream_event_should_have_incremented_sequence() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.withdraw_lockup(&id, &100);
+    vars.contract.withdraw_lockup(&id, &100);
+
+    let receiver = vars.contract.get_lockup(&id).receiver;
+
+    let events = vars.env.events().all();
+    assert!(events.contains((
+        vars.contract.address.clone(),
+        (symbol_short!("LOCKUP"), symbol_short!("CREATED"), receiver).into_val(&vars.env),
+        (id, 0u64, 0u64).into_val(&vars.env)
+    )));
+    assert!(events.contains((
+        vars.contract.address.clone(),
+        (
+            symbol_short!("LOCKUP"),
+            symbol_short!("WITHDRAWN"),
+            vars.admin.clone()
+        )
+            .into_val(&vars.env),
+        (id, 1u64).into_val(&vars.env)
+    )));
+    assert!(events.contains((
+        vars.contract.address.clone(),
+        (
+            symbol_short!("LOCKUP"),
+            symbol_short!("WITHDRAWN"),
+            vars.admin.clone()
+        )
+            .into_val(&vars.env),
+        (id, 2u64).into_val(&vars.env)
+    )));
+}
+
+#[test]
+fn test_can_withdraw_should_be_true_for_the_receiver_of_a_started_stream() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let stream = vars.contract.get_lockup(&id);
+
+    vars.move_ledger_timestamp_to(50);
+
+    assert!(vars.contract.can_withdraw(&id, &stream.receiver));
+    assert!(!vars.contract.can_withdraw(&id, &vars.admin));
+    assert!(!vars
+        .contract
+        .can_withdraw(&id, &Address::generate(&vars.env)));
+}
+
+#[test]
+fn test_can_withdraw_should_be_false_before_start() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        start_date: 10,
+        cliff_date: 10,
+        ..Default::default()
+    });
+
+    let stream = vars.contract.get_lockup(&id);
+
+    vars.move_ledger_timestamp_to(10);
+
+    assert!(!vars.contract.can_withdraw(&id, &stream.receiver));
+}
+
+#[test]
+fn test_can_withdraw_should_be_false_before_cliff() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        cliff_date: 50,
+        ..Default::default()
+    });
+
+    let stream = vars.contract.get_lockup(&id);
+
+    vars.move_ledger_timestamp_to(50);
+    assert!(!vars.contract.can_withdraw(&id, &stream.receiver));
+
+    vars.move_ledger_timestamp_to(51);
+    assert!(vars.contract.can_withdraw(&id, &stream.receiver));
+}
+
+#[test]
+fn test_can_withdraw_should_be_false_when_nothing_is_left() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let stream = vars.contract.get_lockup(&id);
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert!(!vars.contract.can_withdraw(&id, &stream.receiver));
+
+    vars.contract.cancel_lockup(&id, &true);
+
+    assert!(!vars.contract.can_withdraw(&id, &stream.receiver));
+}
+
+#[test]
+fn test_withdrawable_should_accrue_for_tiny_amounts_over_long_durations() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields {
+        amount: 10,
+        end_date: 10_000_000,
+        ..Default::default()
+    });
+
+    let mut previous = 0;
+
+    for timestamp in (0..=10_000_000).step_by(500_000) {
+        vars.move_ledger_timestamp_to(timestamp);
+
+        let withdrawable = vars.contract.get_withdrawable(&id);
+
+        assert!(withdrawable >= previous);
+        previous = withdrawable;
+    }
+
+    vars.move_ledger_timestamp_to(1_000_000);
+    assert_eq!(vars.contract.get_withdrawable(&id), 1);
+
+    vars.move_ledger_timestamp_to(5_500_000);
+    assert_eq!(vars.contract.get_withdrawable(&id), 5);
+}
+
+#[test]
+fn test_withdrawable_should_accrue_for_tiny_vesting_amounts_over_many_periods() {
+    let (vars, id) = SetupStreamTest::setup_with_vesting_created(VestingFields {
+        amount: 150,
+        end_date: 100 * 86400,
+        ..Default::default()
+    });
+
+    // Such a vesting can't be created anymore, but the ones stored before still
+    // have to accrue
+    let mut vesting = vars.contract.get_lockup(&id);
+    vesting.amount = 10;
+    vesting.funded_amount = 10;
+    vars.set_lockup(id, &vesting);
+
+    vars.move_ledger_timestamp_to(10 * 86400);
+    assert_eq!(vars.contract.get_withdrawable(&id), 1);
+
+    vars.move_ledger_timestamp_to(55 * 86400 + 100);
+    assert_eq!(vars.contract.get_withdrawable(&id), 5);
+}
+
+#[test]
+fn test_withdrawable_should_accrue_per_period_for_small_vesting_amounts() {
+    let (vars, id) = SetupStreamTest::setup_with_vesting_created(VestingFields {
+        amount: 150,
+        end_date: 100 * 86400,
+        ..Default::default()
+    });
+
+    vars.move_ledger_timestamp_to(10 * 86400);
+    assert_eq!(vars.contract.get_withdrawable(&id), 15);
+
+    vars.move_ledger_timestamp_to(55 * 86400 + 100);
+    assert_eq!(vars.contract.get_withdrawable(&id), 82);
+}
+
+#[test]
+fn test_get_lockup_withdrawn_should_return_the_total_withdrawn() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    assert_eq!(vars.contract.get_lockup_withdrawn(&id), 0);
+
+    vars.move_ledger_timestamp_to(20);
+    vars.contract.withdraw_lockup(&id, &100);
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.contract.get_lockup_withdrawn(&id), 500);
+}
+
+#[test]
+fn test_get_lockup_withdrawn_should_revert_when_lockup_does_not_exist() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let result = vars.contract.try_get_lockup_withdrawn(&1);
+
+    assert_eq!(result, Err(Ok(CustomErrors::LockupNotFound)));
+}
+
+fn create_stream_with_withdraw_unlock_date(vars: &SetupStreamTest, unlock_date: u64) -> u64 {
+    let now = vars.env.ledger().timestamp();
+
+    let params = LockupInput {
+        options: LockupOptions {
+            withdraw_unlock_date: Some(now + unlock_date),
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_stream(&params)
+}
+
+#[test]
+fn test_withdraw_stream_should_revert_before_withdraw_unlock_date() {
+    let vars = SetupStreamTest::setup(1000);
+    let id = create_stream_with_withdraw_unlock_date(&vars, 150);
+
+    vars.move_ledger_timestamp_to(120);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::WithdrawLocked)));
+    assert_eq!(vars.contract.get_withdrawable(&id), 1000);
+    assert!(!vars
+        .contract
+        .can_withdraw(&id, &vars.contract.get_lockup(&id).receiver));
+}
+
+#[test]
+fn test_withdraw_stream_should_work_after_withdraw_unlock_date() {
+    let vars = SetupStreamTest::setup(1000);
+    let id = create_stream_with_withdraw_unlock_date(&vars, 150);
+
+    vars.move_ledger_timestamp_to(150);
+
+    let amount = vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(amount, 1000);
+    assert_eq!(
+        vars.token.balance(&vars.contract.get_lockup(&id).receiver),
+        1000
+    );
+}
+
+#[test]
+fn test_simulate_withdraw_should_match_the_real_withdraw() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(30);
+
+    let simulated = vars.contract.simulate_withdraw(&id, &100);
+    assert_eq!(simulated, vars.contract.withdraw_lockup(&id, &100));
+
+    vars.move_ledger_timestamp_to(70);
+
+    let simulated = vars.contract.simulate_withdraw(&id, &0);
+    assert_eq!(simulated, 600);
+    assert_eq!(simulated, vars.contract.withdraw_lockup(&id, &0));
+    assert_eq!(vars.contract.get_lockup_withdrawn(&id), 700);
+}
+
+#[test]
+fn test_simulate_withdraw_should_fail_like_the_real_withdraw() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(30);
+
+    let simulated = vars.contract.try_simulate_withdraw(&id, &301);
+
+    assert_eq!(
+        simulated,
+        Err(Ok(CustomErrors::SpecifiedAmountIsGreaterThanWithdrawable))
+    );
+    assert_eq!(simulated, vars.contract.try_withdraw_lockup(&id, &301));
+}
+
+fn create_stream_with_cliff_mode(vars: &SetupStreamTest, cliff_mode: CliffMode) -> u64 {
+    let params = LockupInput {
+        cancellable_date: 0,
+        cliff_date: 50,
+        start_date: 0,
+        end_date: 100,
+        options: LockupOptions {
+            cliff_mode,
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    vars.contract.create_stream(&params)
+}
+
+#[test]
+fn test_withdrawable_should_jump_at_the_cliff_when_gating_from_start() {
+    let vars = SetupStreamTest::setup(1000);
+    let id = create_stream_with_cliff_mode(&vars, CliffMode::GateFromStart);
+
+    for (timestamp, withdrawable) in [(25, 0), (50, 0), (51, 510), (75, 750), (100, 1000)] {
+        vars.move_ledger_timestamp_to(timestamp);
+
+        assert_eq!(vars.contract.get_withdrawable(&id), withdrawable);
+    }
+}
+
+#[test]
+fn test_withdrawable_should_accrue_from_the_cliff_when_linear_from_cliff() {
+    let vars = SetupStreamTest::setup(1000);
+    let id = create_stream_with_cliff_mode(&vars, CliffMode::LinearFromCliff);
+
+    for (timestamp, withdrawable) in [(25, 0), (50, 0), (51, 20), (75, 500), (100, 1000)] {
+        vars.move_ledger_timestamp_to(timestamp);
+
+        assert_eq!(vars.contract.get_withdrawable(&id), withdrawable);
+    }
+}
+
+#[test]
+fn test_withdraw_all_should_sweep_the_receiver_lockups() {
+    let vars = SetupStreamTest::setup(3000);
+    let receiver = Address::generate(&vars.env);
+
+    let mut params = LockupInput {
+        receiver: receiver.clone(),
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        ..vars.lockup_input()
+    };
+
+    let first_id = vars.contract.create_stream(&params);
+
+    params.end_date = 200;
+    let second_id = vars.contract.create_stream(&params);
+
+    params.receiver = Address::generate(&vars.env);
+    let other_id = vars.contract.create_stream(&params);
+
+    vars.move_ledger_timestamp_to(50);
+
+    let ids = vec![&vars.env, first_id, second_id, other_id, 99];
+
+    assert_eq!(vars.contract.withdraw_all(&receiver, &ids), 750);
+    assert_eq!(vars.token.balance(&receiver), 750);
+    assert_eq!(vars.contract.get_lockup(&other_id).withdrawn, 0);
+    assert_eq!(vars.contract.withdraw_all(&receiver, &ids), 0);
+}
+
+#[test]
+fn test_withdraw_should_revert_when_the_withdrawn_accounting_overflows() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    let mut lockup = vars.contract.get_lockup(&id);
+    lockup.amount = i128::MAX;
+    lockup.funded_amount = i128::MAX;
+    lockup.withdrawn = -1;
+
+    vars.set_lockup(id, &lockup);
+    vars.move_ledger_timestamp_to(100);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::AmountOverflows)));
+}
+
+#[test]
+fn test_withdraw_should_not_emit_events_when_disabled() {
+    let vars = SetupStreamTest::setup(1000);
+
+    let params = LockupInput {
+        cancellable_date: 0,
+        cliff_date: 0,
+        start_date: 0,
+        end_date: 100,
+        options: LockupOptions {
+            emit_events: Some(false),
+            ..Default::default()
+        },
+        ..vars.lockup_input()
+    };
+
+    let id = vars.contract.create_stream(&params);
+
+    vars.move_ledger_timestamp_to(50);
+    vars.contract.withdraw_lockup(&id, &100);
+    vars.contract.cancel_lockup(&id, &true);
+
+    let topics = |name| (symbol_short!("LOCKUP"), name, vars.admin.clone()).into_val(&vars.env);
+    let events = vars.env.events().all();
+
+    assert!(!events
+        .iter()
+        .any(|(_, event_topics, _)| event_topics == topics(symbol_short!("WITHDRAWN"))));
+    assert!(events
+        .iter()
+        .any(|(_, event_topics, _)| event_topics == topics(symbol_short!("CANCELLED"))));
+}
+
+#[test]
+fn test_get_withdrawable_at_should_project_net_of_the_withdrawn_amount() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    assert_eq!(vars.contract.get_withdrawable_at(&id, &0), 0);
+    assert_eq!(vars.contract.get_withdrawable_at(&id, &25), 250);
+
+    vars.move_ledger_timestamp_to(40);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    assert_eq!(vars.contract.get_withdrawable_at(&id, &30), 0);
+    assert_eq!(vars.contract.get_withdrawable_at(&id, &70), 300);
+    assert_eq!(vars.contract.get_withdrawable_at(&id, &100), 600);
+    assert_eq!(vars.contract.get_withdrawable_at(&id, &5000), 600);
+}
+
+#[test]
+fn test_get_withdrawable_at_should_stop_at_the_cancellation() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.move_ledger_timestamp_to(40);
+    vars.contract.cancel_lockup(&id, &false);
+
+    assert_eq!(vars.contract.get_withdrawable_at(&id, &1000), 400);
+}
+
+#[test]
+fn test_withdraw_stream_should_emit_the_bucket_of_the_amount() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.initialize();
+    vars.contract.set_withdrawal_buckets(&WithdrawalBuckets {
+        medium_from: 100,
+        large_from: 300,
+    });
+
+    let withdraw_bucket = |timestamp: u64, amount: i128| {
+        vars.move_ledger_timestamp_to(timestamp);
+        let withdrawn = vars.contract.withdraw_lockup(&id, &amount);
+
+        let (_, topics, data) = vars.env.events().all().last().unwrap();
+        let (bucket_id, _, bucket_amount): (u64, u64, i128) = data.into_val(&vars.env);
+
+        assert_eq!((bucket_id, bucket_amount), (id, withdrawn));
+
+        topics
+    };
+
+    let topics = |bucket| (symbol_short!("METRICS"), symbol_short!("WITHDRAWN"), bucket);
+
+    assert_eq!(
+        withdraw_bucket(10, 99),
+        topics(symbol_short!("SMALL")).into_val(&vars.env)
+    );
+    assert_eq!(
+        withdraw_bucket(20, 100),
+        topics(symbol_short!("MEDIUM")).into_val(&vars.env)
+    );
+    assert_eq!(
+        withdraw_bucket(60, 299),
+        topics(symbol_short!("MEDIUM")).into_val(&vars.env)
+    );
+    assert_eq!(
+        withdraw_bucket(100, 0),
+        topics(symbol_short!("LARGE")).into_val(&vars.env)
+    );
+}
+
+#[test]
+fn test_set_withdrawal_buckets_should_revert_when_the_thresholds_are_unordered() {
+    let vars = SetupStreamTest::setup(1000);
+
+    vars.initialize();
+
+    let result = vars
+        .contract
+        .try_set_withdrawal_buckets(&WithdrawalBuckets {
+            medium_from: 300,
+            large_from: 100,
+        });
+
+    assert_eq!(result, Err(Ok(CustomErrors::InvalidAmount)));
+    assert_eq!(vars.contract.get_withdrawal_buckets(), None);
+}
+
+#[test]
+fn test_withdraw_stream_should_revert_below_the_minimum() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.initialize();
+    vars.contract.set_min_withdraw(&100);
+
+    vars.move_ledger_timestamp_to(5);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &0);
+
+    assert_eq!(result, Err(Ok(CustomErrors::WithdrawBelowMinimum)));
+
+    vars.move_ledger_timestamp_to(20);
+
+    let result = vars.contract.try_withdraw_lockup(&id, &99);
+
+    assert_eq!(result, Err(Ok(CustomErrors::WithdrawBelowMinimum)));
+    assert_eq!(vars.contract.withdraw_lockup(&id, &100), 100);
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 100);
+}
+
+#[test]
+fn test_withdraw_stream_should_allow_the_final_withdrawal_below_the_minimum() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.initialize();
+    vars.contract.set_min_withdraw(&100);
+
+    vars.move_ledger_timestamp_to(99);
+    vars.contract.withdraw_lockup(&id, &0);
+
+    vars.move_ledger_timestamp_to(100);
+
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 10);
+    assert_eq!(vars.contract.get_withdrawable(&id), 0);
+}
+
+#[test]
+fn test_withdraw_stream_should_allow_draining_a_cancelled_lockup_below_the_minimum() {
+    let (vars, id) = SetupStreamTest::setup_with_stream_created(StreamFields::default());
+
+    vars.initialize();
+    vars.contract.set_min_withdraw(&100);
+
+    vars.move_ledger_timestamp_to(5);
+    vars.contract.cancel_lockup(&id, &false);
+
+    assert_eq!(vars.contract.withdraw_lockup(&id, &0), 50);
+}